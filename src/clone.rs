@@ -18,7 +18,9 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Sodg;
+use crate::{Sodg, BRANCH_STATIC};
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
 
 impl<const N: usize> Clone for Sodg<N> {
     /// Make a clone of the graph.
@@ -28,12 +30,91 @@ impl<const N: usize> Clone for Sodg<N> {
             branches: self.branches.clone(),
             stores: self.stores.clone(),
             next_v: self.next_v,
+            reverse: self.reverse.clone(),
+            ords: self.ords.clone(),
+            logger: None,
         }
     }
 }
 
+impl<const N: usize> Sodg<N> {
+    /// Make a clone of the graph, changing the IDs of all vertices
+    /// with the provided function.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// let c = g.clone_map(|v| v + 10).unwrap();
+    /// assert_eq!(Some(11), c.kid(10, Label::Alpha(0)));
+    /// ```
+    ///
+    /// The capacity of the new graph is extended, if necessary, to fit
+    /// the new IDs. Edges to absent vertices are not copied.
+    ///
+    /// # Errors
+    ///
+    /// If the function maps two vertices to the same ID, an `Err`
+    /// will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn clone_map(&self, f: impl Fn(usize) -> usize) -> Result<Self> {
+        let mut ids = HashMap::new();
+        for v in self.keys() {
+            let id = f(v);
+            if let Some(before) = ids.insert(id, v) {
+                return Err(anyhow!("Both ν{before} and ν{v} are mapped to ν{id}"));
+            }
+        }
+        let cap = ids
+            .keys()
+            .max()
+            .map_or(0, |m| m + 1)
+            .max(self.vertices.capacity());
+        let mut g = Self::empty(cap);
+        for (id, v) in &ids {
+            let mut vtx = self.vertices.get(*v).unwrap().clone();
+            vtx.edges = vtx
+                .edges
+                .iter()
+                .filter(|(_, to)| self.alive(**to))
+                .map(|(a, to)| (*a, f(*to)))
+                .collect();
+            g.vertices.insert(*id, vtx);
+        }
+        for (b, members) in self.branches.iter() {
+            if b > BRANCH_STATIC {
+                g.branches.insert(
+                    b,
+                    microstack::Stack::from_vec(
+                        members
+                            .into_iter()
+                            .filter(|m| self.alive(*m))
+                            .map(&f)
+                            .collect(),
+                    ),
+                );
+            }
+        }
+        g.stores = self.stores.clone();
+        for ((v, a), o) in &self.ords {
+            if self.alive(*v) && g.kid(f(*v), *a).is_some() {
+                g.ords.insert((f(*v), *a), *o);
+            }
+        }
+        g.enable_reverse_index(self.reverse.is_some());
+        Ok(g)
+    }
+}
+
 #[cfg(test)]
-use crate::Label;
+use crate::{Hex, Label};
 
 #[test]
 fn makes_a_clone() {
@@ -46,8 +127,26 @@ fn makes_a_clone() {
 }
 
 #[test]
+#[allow(clippy::redundant_clone)]
 fn makes_an_empty_clone() {
     let g: Sodg<16> = Sodg::empty(256);
     let c = g.clone();
     assert_eq!(0, c.len());
 }
+
+#[test]
+fn clones_with_new_ids() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(1, 2, Label::Alpha(1));
+    g.put(2, &Hex::from(42));
+    let mut c = g.clone_map(|v| v + 300).unwrap();
+    assert_eq!(3, c.len());
+    assert_eq!(Some(301), c.kid(300, Label::Alpha(0)));
+    assert_eq!(Some(302), c.kid(301, Label::Alpha(1)));
+    assert_eq!(42, c.data(302).unwrap().to_i64().unwrap());
+    assert!(g.clone_map(|v| v / 2).is_err());
+}
@@ -0,0 +1,101 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Sodg;
+use std::collections::HashMap;
+
+impl<const N: usize> Sodg<N> {
+    /// Count weakly-connected components of the graph, treating all
+    /// edges as undirected.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.add(2);
+    /// assert_eq!(2, g.component_count());
+    /// ```
+    ///
+    /// A single vertex without edges is a component on its own.
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        let mut parent: HashMap<usize, usize> = self.keys().into_iter().map(|v| (v, v)).collect();
+        let mut total = parent.len();
+        for v in self.keys() {
+            for (_, to) in self.kids(v) {
+                if !parent.contains_key(to) {
+                    continue;
+                }
+                let left = Self::union_root(&mut parent, v);
+                let right = Self::union_root(&mut parent, *to);
+                if left != right {
+                    parent.insert(left, right);
+                    total -= 1;
+                }
+            }
+        }
+        total
+    }
+
+    /// Find the root of the set where `v` belongs, compressing the path.
+    fn union_root(parent: &mut HashMap<usize, usize>, v: usize) -> usize {
+        let mut root = v;
+        while parent[&root] != root {
+            root = parent[&root];
+        }
+        let mut x = v;
+        while x != root {
+            let next = parent[&x];
+            parent.insert(x, root);
+            x = next;
+        }
+        root
+    }
+}
+
+#[cfg(test)]
+use crate::Label;
+
+#[test]
+fn counts_two_clusters() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(2, 1, Label::Alpha(0));
+    g.add(3);
+    g.add(4);
+    g.bind(4, 3, Label::Alpha(0));
+    assert_eq!(2, g.component_count());
+}
+
+#[test]
+fn counts_isolated_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    assert_eq!(0, g.component_count());
+    g.add(0);
+    g.add(7);
+    assert_eq!(2, g.component_count());
+}
@@ -20,6 +20,7 @@
 
 use crate::{Hex, Persistence, Sodg, Vertex, MAX_BRANCHES};
 use emap::Map;
+use std::collections::HashMap;
 
 impl<const N: usize> Sodg<N> {
     /// Make an empty [`Sodg`], with no vertices and no edges.
@@ -42,6 +43,9 @@ impl<const N: usize> Sodg<N> {
             stores: Map::with_capacity_some(MAX_BRANCHES, 0),
             branches: Map::with_capacity_some(MAX_BRANCHES, microstack::Stack::new()),
             next_v: 0,
+            reverse: Some(HashMap::new()),
+            ords: HashMap::new(),
+            logger: None,
         };
         g.branches
             .insert(0, microstack::Stack::from_vec([0].to_vec()));
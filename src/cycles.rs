@@ -0,0 +1,304 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Sodg;
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+impl<const N: usize> Sodg<N> {
+    /// Find the girth of the graph, which is the length of its shortest
+    /// directed cycle.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.bind(1, 2, Label::Alpha(0));
+    /// g.bind(2, 0, Label::Alpha(0));
+    /// assert_eq!(Some(3), g.girth());
+    /// ```
+    ///
+    /// A vertex bound to itself makes a cycle of length one. If there
+    /// are no cycles in the graph at all, `None` is returned.
+    #[must_use]
+    pub fn girth(&self) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        for v in self.keys() {
+            let mut dist = HashMap::new();
+            let mut queue = VecDeque::new();
+            dist.insert(v, 0);
+            queue.push_back(v);
+            while let Some(u) = queue.pop_front() {
+                let d = dist[&u] + 1;
+                if best.is_some_and(|b| d >= b) {
+                    break;
+                }
+                for (_, to) in self.kids(u) {
+                    if *to == v {
+                        best = Some(d);
+                        break;
+                    }
+                    if !self.alive(*to) {
+                        continue;
+                    }
+                    if !dist.contains_key(to) {
+                        dist.insert(*to, d);
+                        queue.push_back(*to);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Make sure there are no directed cycles in the graph.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert!(g.assert_acyclic().is_ok());
+    /// g.bind(1, 0, Label::Alpha(0));
+    /// assert!(g.assert_acyclic().is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there is a cycle, an `Err` will be returned, with
+    /// the vertices of one of the cycles found.
+    pub fn assert_acyclic(&self) -> Result<()> {
+        self.find_cycle().map_or(Ok(()), |cycle| {
+            Err(anyhow!(
+                "There is a cycle in the graph: {}",
+                cycle
+                    .iter()
+                    .chain(cycle.first())
+                    .map(|v| format!("ν{v}"))
+                    .collect::<Vec<String>>()
+                    .join(" → ")
+            ))
+        })
+    }
+
+    /// Check whether there are no directed cycles in the graph.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert!(g.is_acyclic());
+    /// g.bind(1, 0, Label::Alpha(0));
+    /// assert!(!g.is_acyclic());
+    /// ```
+    ///
+    /// Use [`Sodg::assert_acyclic`] to find out which vertices make the cycle.
+    #[must_use]
+    pub fn is_acyclic(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    /// Count, for every vertex, the number of distinct vertices
+    /// reachable from it, including the vertex itself.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.bind(0, 2, Label::Alpha(1));
+    /// g.bind(1, 2, Label::Alpha(0));
+    /// let sizes = g.subtree_sizes().unwrap();
+    /// assert_eq!(3, sizes[&0]);
+    /// assert_eq!(2, sizes[&1]);
+    /// ```
+    ///
+    /// All sizes are calculated in one post-order pass, which is
+    /// much cheaper than asking for them one by one. A vertex
+    /// reachable by a few paths is counted only once.
+    ///
+    /// # Errors
+    ///
+    /// If there is a cycle in the graph, an `Err` will be returned.
+    pub fn subtree_sizes(&self) -> Result<HashMap<usize, usize>> {
+        self.assert_acyclic()?;
+        let mut below: HashMap<usize, HashSet<usize>> = HashMap::new();
+        for root in self.keys() {
+            let mut todo = vec![(root, false)];
+            while let Some((v, ready)) = todo.pop() {
+                if below.contains_key(&v) {
+                    continue;
+                }
+                let kids = self.alive_kids(v);
+                if ready {
+                    let mut all = HashSet::from([v]);
+                    for k in kids {
+                        all.extend(&below[&k]);
+                    }
+                    below.insert(v, all);
+                } else {
+                    todo.push((v, true));
+                    todo.extend(
+                        kids.into_iter()
+                            .filter(|k| !below.contains_key(k))
+                            .map(|k| (k, false)),
+                    );
+                }
+            }
+        }
+        Ok(below.into_iter().map(|(v, all)| (v, all.len())).collect())
+    }
+
+    /// Find any directed cycle in the graph and return its vertices,
+    /// in the order of edges between them.
+    fn find_cycle(&self) -> Option<Vec<usize>> {
+        let mut done = HashSet::new();
+        for root in self.keys().into_iter().sorted() {
+            if done.contains(&root) {
+                continue;
+            }
+            let mut path: Vec<usize> = vec![root];
+            let mut pending: Vec<Vec<usize>> = vec![self.alive_kids(root)];
+            while let Some(kids) = pending.last_mut() {
+                if let Some(k) = kids.pop() {
+                    if let Some(pos) = path.iter().position(|p| *p == k) {
+                        return Some(path[pos..].to_vec());
+                    }
+                    if !done.contains(&k) {
+                        path.push(k);
+                        pending.push(self.alive_kids(k));
+                    }
+                } else {
+                    done.insert(path.pop()?);
+                    pending.pop();
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the IDs of all alive kids of the vertex, in reverse order.
+    fn alive_kids(&self, v: usize) -> Vec<usize> {
+        self.kids(v)
+            .map(|(_, to)| *to)
+            .filter(|to| self.alive(*to))
+            .sorted()
+            .rev()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+use crate::Label;
+
+#[test]
+fn finds_girth_of_self_loop() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(1, 2, Label::Alpha(0));
+    g.bind(2, 0, Label::Alpha(0));
+    g.add(3);
+    g.bind(3, 3, Label::Alpha(0));
+    assert_eq!(Some(1), g.girth());
+}
+
+#[test]
+fn finds_no_girth_in_dag() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 2, Label::Alpha(1));
+    g.bind(1, 2, Label::Alpha(0));
+    assert_eq!(None, g.girth());
+}
+
+#[test]
+fn finds_cycle_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.add(3);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 2, Label::Alpha(1));
+    g.bind(1, 2, Label::Alpha(0));
+    g.bind(2, 3, Label::Alpha(0));
+    assert!(g.assert_acyclic().is_ok());
+    g.bind(3, 1, Label::Alpha(0));
+    let msg = g.assert_acyclic().unwrap_err().to_string();
+    assert!(msg.contains("ν1 → ν2 → ν3 → ν1"), "{msg}");
+    assert!(!msg.contains("ν0"), "{msg}");
+}
+
+#[test]
+fn detects_two_vertex_cycle() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(1, 2, Label::Alpha(0));
+    assert!(g.is_acyclic());
+    g.bind(2, 1, Label::Alpha(0));
+    assert!(!g.is_acyclic());
+    assert!(g.validate().is_ok());
+}
+
+#[test]
+fn counts_subtree_sizes() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..6 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 2, Label::Alpha(1));
+    g.bind(1, 3, Label::Alpha(0));
+    g.bind(1, 4, Label::Alpha(1));
+    g.bind(2, 5, Label::Alpha(0));
+    let sizes = g.subtree_sizes().unwrap();
+    assert_eq!(6, sizes[&0]);
+    assert_eq!(3, sizes[&1]);
+    assert_eq!(2, sizes[&2]);
+    assert_eq!(1, sizes[&4]);
+    g.bind(5, 0, Label::Alpha(0));
+    assert!(g.subtree_sizes().is_err());
+}
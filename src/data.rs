@@ -0,0 +1,504 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Hex, Persistence, Sodg};
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+
+impl<const N: usize> Sodg<N> {
+    /// Check whether the vertex `v` has data, without submitting
+    /// it to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// assert!(!g.has_data(0));
+    /// g.put(0, &Hex::from(42));
+    /// assert!(g.has_data(0));
+    /// ```
+    ///
+    /// If the vertex is absent, `false` is returned.
+    #[must_use]
+    pub fn has_data(&self, v: usize) -> bool {
+        self.alive(v)
+            && self
+                .vertices
+                .get(v)
+                .is_some_and(|vtx| vtx.persistence != Persistence::Empty)
+    }
+
+    /// Count the vertices that have non-empty data, without submitting
+    /// them to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.put(1, &Hex::from(42));
+    /// assert_eq!(1, g.count_data_vertices());
+    /// ```
+    ///
+    /// A vertex with empty data, like [`Hex::empty`], is not counted.
+    #[must_use]
+    pub fn count_data_vertices(&self) -> usize {
+        self.vertices
+            .iter()
+            .filter(|(v, vtx)| self.has_data(*v) && !vtx.data.is_empty())
+            .count()
+    }
+
+    /// Count the vertices by the length of their data, without
+    /// submitting them to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.put(1, &Hex::from(42));
+    /// let h = g.data_len_histogram();
+    /// assert_eq!(1, h[&0]);
+    /// assert_eq!(1, h[&8]);
+    /// ```
+    ///
+    /// A vertex without data is counted as a vertex with data of zero length.
+    #[must_use]
+    pub fn data_len_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut h = BTreeMap::new();
+        for (_, vtx) in self.vertices.iter().filter(|(v, _)| self.alive(*v)) {
+            *h.entry(vtx.data.len()).or_insert(0) += 1;
+        }
+        h
+    }
+
+    /// Check whether the data of the vertex `v` starts with the
+    /// provided bytes, without submitting the vertex to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from_str_bytes("hello"));
+    /// assert!(g.data_starts_with(0, b"he").unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent, an `Err` will be returned.
+    pub fn data_starts_with(&self, v: usize, prefix: &[u8]) -> Result<bool> {
+        Ok(self.bytes_of(v)?.starts_with(prefix))
+    }
+
+    /// Check whether the data of the vertex `v` ends with the
+    /// provided bytes, without submitting the vertex to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from_str_bytes("hello"));
+    /// assert!(g.data_ends_with(0, b"llo").unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent, an `Err` will be returned.
+    pub fn data_ends_with(&self, v: usize, suffix: &[u8]) -> Result<bool> {
+        Ok(self.bytes_of(v)?.ends_with(suffix))
+    }
+
+    /// Check whether the vertices `a` and `b` have exactly the same data,
+    /// without submitting them to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// assert!(g.data_equal(0, 1).unwrap());
+    /// g.put(0, &Hex::from(42));
+    /// assert!(!g.data_equal(0, 1).unwrap());
+    /// ```
+    ///
+    /// A vertex without data is treated as a vertex with empty data.
+    ///
+    /// # Errors
+    ///
+    /// If either vertex is absent, an `Err` will be returned.
+    pub fn data_equal(&self, a: usize, b: usize) -> Result<bool> {
+        Ok(self.bytes_of(a)? == self.bytes_of(b)?)
+    }
+
+    /// Read the data of the vertex `v` as an integer, stored as eight
+    /// big-endian bytes, without submitting the vertex to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from(42));
+    /// assert_eq!(42, g.data_as_i64(0).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent or its data is not eight bytes long,
+    /// an `Err` will be returned.
+    pub fn data_as_i64(&self, v: usize) -> Result<i64> {
+        Hex::from_slice(self.bytes_of(v)?)
+            .to_i64()
+            .with_context(|| format!("Can't read ν{v} as an integer"))
+    }
+
+    /// Read the data of the vertex `v` as a float, stored as eight
+    /// big-endian bytes, without submitting the vertex to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from(3.14));
+    /// assert_eq!(3.14, g.data_as_f64(0).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent or its data is not eight bytes long,
+    /// an `Err` will be returned.
+    pub fn data_as_f64(&self, v: usize) -> Result<f64> {
+        Hex::from_slice(self.bytes_of(v)?)
+            .to_f64()
+            .with_context(|| format!("Can't read ν{v} as a float"))
+    }
+
+    /// Remove the data from the vertex `v`, keeping the vertex and its edges.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from(42));
+    /// g.clear_data(0).unwrap();
+    /// assert!(!g.has_data(0));
+    /// assert_eq!(1, g.len());
+    /// ```
+    ///
+    /// Unlike [`Sodg::data`], this doesn't submit the vertex to garbage collection.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn clear_data(&mut self, v: usize) -> Result<()> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v}"));
+        }
+        let vtx = self.vertices.get_mut(v).unwrap();
+        if vtx.persistence == Persistence::Stored {
+            *self.stores.get_mut(vtx.branch).unwrap() -= 1;
+        }
+        vtx.persistence = Persistence::Empty;
+        vtx.data = Hex::empty();
+        Ok(())
+    }
+
+    /// Exchange the data of the vertices `a` and `b`, without touching their edges.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.put(0, &Hex::from(42));
+    /// g.swap_data(0, 1).unwrap();
+    /// assert!(!g.has_data(0));
+    /// assert_eq!(42, g.data(1).unwrap().to_i64().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If either vertex is absent, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn swap_data(&mut self, a: usize, b: usize) -> Result<()> {
+        for v in [a, b] {
+            if !self.alive(v) {
+                return Err(anyhow!("Can't find ν{v}"));
+            }
+        }
+        if a == b {
+            return Ok(());
+        }
+        let take = |g: &mut Self, v: usize| {
+            let vtx = g.vertices.get_mut(v).unwrap();
+            let p = std::mem::replace(&mut vtx.persistence, Persistence::Empty);
+            if p == Persistence::Stored {
+                *g.stores.get_mut(vtx.branch).unwrap() -= 1;
+            }
+            (std::mem::replace(&mut vtx.data, Hex::empty()), p)
+        };
+        let give = |g: &mut Self, v: usize, (d, p): (Hex, Persistence)| {
+            let vtx = g.vertices.get_mut(v).unwrap();
+            if p == Persistence::Stored {
+                *g.stores.get_mut(vtx.branch).unwrap() += 1;
+            }
+            vtx.data = d;
+            vtx.persistence = p;
+        };
+        let left = take(self, a);
+        let right = take(self, b);
+        give(self, a, right);
+        give(self, b, left);
+        Ok(())
+    }
+
+    /// Replace the data of every vertex that matches the predicate
+    /// with the `replacement`, and return the number of vertices changed.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from_str_bytes("secret"));
+    /// g.add(1);
+    /// assert_eq!(1, g.redact(|d| d.len() > 4, Hex::from_str_bytes("***")));
+    /// assert_eq!("***", g.data(0).unwrap().to_utf8().unwrap());
+    /// ```
+    ///
+    /// Vertices without data are never passed to the predicate. Neither
+    /// the edges nor the garbage collection state of the vertices are touched.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn redact(&mut self, pred: impl Fn(&Hex) -> bool, replacement: Hex) -> usize {
+        let mut total = 0;
+        for v in self.keys() {
+            let vtx = self.vertices.get_mut(v).unwrap();
+            if vtx.persistence != Persistence::Empty && pred(&vtx.data) {
+                vtx.data = replacement.clone();
+                total += 1;
+            }
+        }
+        total
+    }
+
+    /// Get the IDs of all vertices, sorted by the length of their data,
+    /// without submitting them to garbage collection.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.put(1, &Hex::from_str_bytes("hello"));
+    /// assert_eq!(vec![1, 0], g.by_data_len(true));
+    /// assert_eq!(vec![0, 1], g.by_data_len(false));
+    /// ```
+    ///
+    /// A vertex without data is treated as a vertex with empty data.
+    /// Vertices with data of the same length are sorted by their IDs,
+    /// in ascending order, no matter the direction.
+    #[must_use]
+    pub fn by_data_len(&self, descending: bool) -> Vec<usize> {
+        let mut keys: Vec<(usize, usize)> = self
+            .vertices
+            .iter()
+            .filter(|(v, _)| self.alive(*v))
+            .map(|(v, vtx)| (vtx.data.len(), v))
+            .collect();
+        keys.sort_unstable_by(|(l1, v1), (l2, v2)| {
+            (if descending { l2.cmp(l1) } else { l1.cmp(l2) }).then(v1.cmp(v2))
+        });
+        keys.into_iter().map(|(_, v)| v).collect()
+    }
+
+    /// Get the bytes of the data in the vertex `v`, without
+    /// submitting the vertex to garbage collection.
+    pub(crate) fn bytes_of(&self, v: usize) -> Result<&[u8]> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v}"));
+        }
+        Ok(self.vertices.get(v).unwrap().data.bytes())
+    }
+}
+
+#[test]
+fn checks_data_prefix() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from_slice(&[0xCA, 0xFE, 0xBA, 0xBE]));
+    assert!(g.data_starts_with(0, &[0xCA, 0xFE]).unwrap());
+    assert!(!g.data_starts_with(0, &[0xBA, 0xBE]).unwrap());
+    assert!(g.data_starts_with(1, &[0xCA]).is_err());
+}
+
+#[test]
+fn checks_data_suffix() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from_slice(&[0xCA, 0xFE, 0xBA, 0xBE]));
+    assert!(g.data_ends_with(0, &[0xBA, 0xBE]).unwrap());
+    assert!(!g.data_ends_with(0, &[0xCA, 0xFE]).unwrap());
+    assert!(g.data_ends_with(1, &[0xBE]).is_err());
+}
+
+#[test]
+fn compares_data_of_two_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    assert!(g.data_equal(0, 1).unwrap());
+    g.put(0, &Hex::from_str_bytes("foo"));
+    g.put(1, &Hex::from_str_bytes("foo"));
+    g.put(2, &Hex::from_str_bytes("bar"));
+    assert!(g.data_equal(0, 1).unwrap());
+    assert!(!g.data_equal(0, 2).unwrap());
+    assert!(g.data_equal(0, 3).is_err());
+}
+
+#[test]
+fn swaps_data_of_two_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.put(0, &Hex::from_str_bytes("foo"));
+    g.put(1, &Hex::from_str_bytes("bar"));
+    g.swap_data(0, 1).unwrap();
+    assert_eq!("bar", g.data(0).unwrap().to_utf8().unwrap());
+    assert_eq!("foo", g.data(1).unwrap().to_utf8().unwrap());
+    assert!(g.swap_data(0, 2).is_err());
+}
+
+#[cfg(test)]
+use crate::Script;
+
+#[test]
+fn reads_typed_numbers() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); PUT(0, -7i64); ADD(1); PUT(1, 2.5f64);");
+    s.deploy_to(&mut g).unwrap();
+    g.add(2);
+    g.put(2, &Hex::from_str_bytes("short"));
+    g.add(3);
+    assert_eq!(-7, g.data_as_i64(0).unwrap());
+    assert!((g.data_as_f64(1).unwrap() - 2.5).abs() < f64::EPSILON);
+    assert!(g.data_as_i64(2).is_err());
+    assert!(g.data_as_f64(3).is_err());
+    assert!(g.data_as_i64(4).is_err());
+}
+
+#[test]
+fn redacts_long_payloads() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from_str_bytes("password"));
+    g.add(1);
+    g.put(1, &Hex::from_str_bytes("abc"));
+    g.add(2);
+    g.put(2, &Hex::from_str_bytes("top secret"));
+    g.add(3);
+    let mask = Hex::from_str_bytes("XX");
+    assert_eq!(2, g.redact(|d| d.len() > 4, mask.clone()));
+    assert_eq!("XX", g.data(0).unwrap().to_utf8().unwrap());
+    assert_eq!("abc", g.data(1).unwrap().to_utf8().unwrap());
+    assert_eq!("XX", g.data(2).unwrap().to_utf8().unwrap());
+    assert!(!g.has_data(3));
+    assert_eq!(0, g.redact(|d| d.len() > 4, mask));
+}
+
+#[test]
+fn sorts_vertices_by_data_length() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for (v, d) in [(0, "ab"), (1, "abcdef"), (2, ""), (3, "ab"), (4, "abc")] {
+        g.add(v);
+        if !d.is_empty() {
+            g.put(v, &Hex::from_str_bytes(d));
+        }
+    }
+    g.add(5);
+    assert_eq!(vec![1, 4, 0, 3, 2, 5], g.by_data_len(true));
+    assert_eq!(vec![2, 5, 0, 3, 4, 1], g.by_data_len(false));
+}
+
+#[test]
+fn counts_vertices_with_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..6 {
+        g.add(v);
+    }
+    g.put(0, &Hex::from(1));
+    g.put(2, &Hex::from_str_bytes("two"));
+    g.put(3, &Hex::empty());
+    g.put(4, &Hex::from(4));
+    g.clear_data(4).unwrap();
+    g.put(5, &Hex::from(5));
+    g.remove(5);
+    assert_eq!(2, g.count_data_vertices());
+}
+
+#[test]
+fn builds_histogram_of_data_lengths() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for (v, d) in ["", "a", "bc", "de", "fgh", "", "ij"]
+        .into_iter()
+        .enumerate()
+    {
+        g.add(v);
+        if !d.is_empty() {
+            g.put(v, &Hex::from_str_bytes(d));
+        }
+    }
+    let expected: BTreeMap<usize, usize> = [(0, 2), (1, 1), (2, 3), (3, 1)].into();
+    assert_eq!(expected, g.data_len_histogram());
+}
@@ -18,9 +18,12 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::{Label, Persistence, Sodg};
+use crate::{Hex, Label, Persistence, Sodg};
 use itertools::Itertools;
 
+/// How many characters (or bytes) of data are shown in a vertex.
+const PREVIEW: usize = 12;
+
 impl<const N: usize> Sodg<N> {
     /// Print SODG as a DOT graph.
     ///
@@ -44,12 +47,16 @@ impl<const N: usize> Sodg<N> {
     ///
     /// ```text
     /// digraph {
-    ///   v0[shape=circle,label="ν0"];
+    ///   v0[shape=circle,label="ν0\n\"hello\""];
     ///   v0 -> v1 [label="bar"];
     ///   v0 -> v1 [label="foo"];
     ///   v1[shape=circle,label="ν1"];
     /// }
     /// ```
+    ///
+    /// A vertex with data shows a preview of it: either the text, if the data
+    /// is a printable UTF-8 string, or the first bytes in hexadecimal format.
+    /// Quotes and other special characters are escaped.
     #[must_use]
     pub fn to_dot(&self) -> String {
         let mut lines: Vec<String> = vec![];
@@ -66,7 +73,12 @@ digraph {
             .sorted_by_key(|(v, _)| <usize>::clone(v))
         {
             lines.push(format!(
-                "  v{v}[shape=circle,label=\"ν{v}\"{}]; {}",
+                "  v{v}[shape=circle,label=\"ν{v}{}\"{}]; {}",
+                if vtx.persistence == Persistence::Empty {
+                    String::new()
+                } else {
+                    format!("\\n{}", escape(&preview(&vtx.data)))
+                },
                 if vtx.persistence == Persistence::Empty {
                     ""
                 } else {
@@ -82,26 +94,18 @@ digraph {
                 lines.push(format!(
                     "  v{v} -> v{} [label=\"{}\"{}{}];",
                     e.1,
-                    e.0,
+                    escape(&e.0.to_string()),
                     match e.0 {
-                        Label::Greek(g) => {
-                            if *g == 'ρ' || *g == 'σ' {
-                                ",color=gray,fontcolor=gray"
-                            } else {
-                                ""
-                            }
+                        Label::Greek(g) if *g == 'ρ' || *g == 'σ' => {
+                            ",color=gray,fontcolor=gray"
                         }
                         _ => {
                             ""
                         }
                     },
                     match e.0 {
-                        Label::Greek(g) => {
-                            if *g == 'π' {
-                                ",style=dashed"
-                            } else {
-                                ""
-                            }
+                        Label::Greek(g) if *g == 'π' => {
+                            ",style=dashed"
                         }
                         _ => {
                             ""
@@ -115,8 +119,33 @@ digraph {
     }
 }
 
+/// Make a short human-readable preview of the data.
+fn preview(d: &Hex) -> String {
+    match d.to_utf8() {
+        Ok(txt) if !txt.chars().any(char::is_control) => {
+            let short: String = txt.chars().take(PREVIEW).collect();
+            format!(
+                "\"{short}{}\"",
+                if short.len() < txt.len() { "…" } else { "" }
+            )
+        }
+        _ => {
+            let bytes = d.bytes();
+            let short = Hex::from_slice(&bytes[..bytes.len().min(PREVIEW)]);
+            format!("{short}{}", if bytes.len() > PREVIEW { "…" } else { "" })
+        }
+    }
+}
+
+/// Escape a string to be printed inside double quotes in DOT.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
 #[cfg(test)]
-use crate::Hex;
+use crate::Script;
 
 #[test]
 fn simple_graph_to_dot() {
@@ -126,5 +155,28 @@ fn simple_graph_to_dot() {
     g.add(1);
     g.bind(0, 1, Label::Alpha(0));
     let dot = g.to_dot();
-    assert!(dot.contains("shape=circle,label=\"ν0\""));
+    assert!(dot.contains("shape=circle,label=\"ν0\\n\\\"hello\\\"\""));
+}
+
+#[test]
+fn prints_script_result_to_dot() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "ADD(0); ADD($ν1); BIND(ν0, $ν1, foo); PUT($ν1, d0-bf-D1-80-d0-B8-d0-b2-d0-b5-d1-82);",
+    );
+    s.deploy_to(&mut g).unwrap();
+    let dot = g.to_dot();
+    assert!(dot.contains("v0 -> v1 [label=\"foo\"];"), "{dot}");
+    assert!(dot.contains("label=\"ν1\\n\\\"привет\\\"\""), "{dot}");
+}
+
+#[test]
+fn previews_binary_data() {
+    assert_eq!("\"hello\"", preview(&Hex::from_str_bytes("hello")));
+    assert_eq!("00-01", preview(&Hex::from_slice(&[0x00, 0x01])));
+    assert_eq!(
+        "\"abcdefghijkl…\"",
+        preview(&Hex::from_str_bytes("abcdefghijklmnop"))
+    );
+    assert_eq!("a\\\"b\\\\", escape("a\"b\\"));
 }
@@ -0,0 +1,496 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Label, Sodg};
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+impl<const N: usize> Sodg<N> {
+    /// Collect all edges of the graph into a vector of
+    /// `(from, label, to)` triples, sorted by `from` and then by `label`.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert_eq!(vec![(0, Label::Alpha(0), 1)], g.to_edge_vec());
+    /// ```
+    #[must_use]
+    pub fn to_edge_vec(&self) -> Vec<(usize, Label, usize)> {
+        self.keys()
+            .into_iter()
+            .flat_map(|v| self.kids(v).map(move |(a, to)| (v, *a, *to)))
+            .sorted_by_key(|(v, a, _)| (*v, *a))
+            .collect()
+    }
+
+    /// Count the edges departing from each vertex of the graph.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// let counts = g.edge_counts();
+    /// assert_eq!(1, counts[&0]);
+    /// assert_eq!(0, counts[&1]);
+    /// ```
+    ///
+    /// Every vertex is present in the map, even if it has no edges.
+    #[must_use]
+    pub fn edge_counts(&self) -> BTreeMap<usize, usize> {
+        self.keys()
+            .into_iter()
+            .map(|v| (v, self.kids(v).count()))
+            .collect()
+    }
+
+    /// Find labels of all edges departing from the vertex `from`
+    /// and arriving to the vertex `to`, in sorted order.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(1));
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert_eq!(vec![Label::Alpha(0), Label::Alpha(1)], g.edges_between(0, 1).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If either vertex is absent, an `Err` will be returned.
+    pub fn edges_between(&self, from: usize, to: usize) -> Result<Vec<Label>> {
+        for v in [from, to] {
+            if !self.alive(v) {
+                return Err(anyhow!("Can't find ν{v}"));
+            }
+        }
+        Ok(self
+            .kids(from)
+            .filter(|(_, t)| **t == to)
+            .map(|(a, _)| *a)
+            .sorted()
+            .collect())
+    }
+
+    /// Remove all edges for which the predicate returns `false`, keeping
+    /// the vertices intact, and return the number of edges removed.
+    ///
+    /// The predicate gets the source vertex, the label of the edge,
+    /// and the target vertex. For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.bind(0, 1, Label::Alpha(1));
+    /// assert_eq!(1, g.retain_edges(|_, a, _| a != Label::Alpha(0)));
+    /// assert_eq!(1, g.kids(0).count());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn retain_edges(&mut self, p: impl Fn(usize, Label, usize) -> bool) -> usize {
+        let mut removed = vec![];
+        for v in self.keys() {
+            for (a, to) in self.kids(v) {
+                if !p(v, *a, *to) {
+                    removed.push((v, *a));
+                }
+            }
+        }
+        for (v, a) in &removed {
+            self.drop_edge(*v, *a);
+        }
+        removed.len()
+    }
+
+    /// Remove all edges that point to vertices that don't exist
+    /// in the graph, and return the number of edges removed.
+    ///
+    /// Normally, there are no such edges. However, a graph may get into
+    /// this bad state, for example, after garbage collection or removal
+    /// of vertices. For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert_eq!(0, g.fix_dangling());
+    /// ```
+    pub fn fix_dangling(&mut self) -> usize {
+        let alive: HashSet<usize> = self.keys().into_iter().collect();
+        self.retain_edges(|_, _, to| alive.contains(&to))
+    }
+
+    /// Make sure all edges of the graph point to vertices that exist.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert!(g.validate().is_ok());
+    /// ```
+    ///
+    /// Use [`Sodg::fix_dangling`] to remove such edges and
+    /// [`Sodg::is_acyclic`] to check for directed cycles.
+    ///
+    /// # Errors
+    ///
+    /// If an edge points to an absent vertex, an `Err` will be returned,
+    /// mentioning the first such edge, in the order of [`Sodg::to_edge_vec`].
+    pub fn validate(&self) -> Result<()> {
+        self.to_edge_vec()
+            .into_iter()
+            .find(|(_, _, to)| !self.alive(*to))
+            .map_or(Ok(()), |(v, a, to)| {
+                Err(anyhow!(
+                    "The edge ν{v}.{a} points to ν{to}, which is absent"
+                ))
+            })
+    }
+
+    /// Rename labels of all edges in the graph, according to the map,
+    /// and return the number of edges renamed.
+    ///
+    /// All renames are applied at once, so labels may be swapped. For example:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// let map = HashMap::from([(Label::Alpha(0), Label::Alpha(1))]);
+    /// assert_eq!(1, g.rename_attrs(&map).unwrap());
+    /// assert_eq!(Some(1), g.kid(0, Label::Alpha(1)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If, after renaming, a vertex would have two edges with the same
+    /// label, an `Err` will be returned and nothing will be changed.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn rename_attrs(&mut self, map: &HashMap<Label, Label>) -> Result<usize> {
+        let mut plan = vec![];
+        for v in self.keys() {
+            let mut seen = HashSet::new();
+            for (a, to) in self.kids(v) {
+                let after = map.get(a).copied().unwrap_or(*a);
+                if !seen.insert(after) {
+                    return Err(anyhow!(
+                        "After renaming, there would be two edges '{after}' in ν{v}"
+                    ));
+                }
+                if after != *a {
+                    plan.push((v, *a, after, *to, self.ord(v, *a)));
+                }
+            }
+        }
+        for (v, a, _, _, _) in &plan {
+            self.drop_edge(*v, *a);
+        }
+        for (v, _, after, to, ord) in &plan {
+            self.put_edge(*v, *after, *to, *ord);
+        }
+        Ok(plan.len())
+    }
+
+    /// Rename the edges labeled `from` to `to`, but only those for which
+    /// the predicate, given the source and the target, returns `true`,
+    /// and return the number of edges renamed.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.bind(1, 0, Label::Alpha(0));
+    /// assert_eq!(1, g.rename_attr_where(Label::Alpha(0), Label::Alpha(1), |v, _| v == 0).unwrap());
+    /// assert_eq!(Some(1), g.kid(0, Label::Alpha(1)));
+    /// assert_eq!(Some(0), g.kid(1, Label::Alpha(0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a vertex to be renamed already has an edge labeled `to`,
+    /// an `Err` will be returned and nothing will be changed.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn rename_attr_where(
+        &mut self,
+        from: Label,
+        to: Label,
+        pred: impl Fn(usize, usize) -> bool,
+    ) -> Result<usize> {
+        if from == to {
+            return Ok(0);
+        }
+        let mut plan = vec![];
+        for v in self.keys() {
+            if let Some(k) = self.kid(v, from).filter(|k| pred(v, *k)) {
+                if self.kid(v, to).is_some() {
+                    return Err(anyhow!("There is already an edge '{to}' in ν{v}"));
+                }
+                plan.push((v, k));
+            }
+        }
+        for (v, k) in &plan {
+            let ord = self.ord(*v, from);
+            self.drop_edge(*v, from);
+            self.put_edge(*v, to, *k, ord);
+        }
+        Ok(plan.len())
+    }
+
+    /// Make the edge `v.a ➞ to` with the ordinal `ord`, replacing the
+    /// edge `v.a`, if it exists, and keeping the reverse index in sync.
+    ///
+    /// All changes of edges must go through this function
+    /// and [`Sodg::drop_edge`], so that no ordinals are left behind.
+    pub(crate) fn put_edge(&mut self, v: usize, a: Label, to: usize, ord: usize) {
+        let vtx = self.vertices.get_mut(v).unwrap();
+        let before = vtx.edges.get(&a).copied();
+        vtx.edges.insert(a, to);
+        if let Some(b) = before {
+            self.unindex_edge(v, a, b);
+        }
+        self.index_edge(v, a, to);
+        if ord == 0 {
+            self.ords.remove(&(v, a));
+        } else {
+            self.ords.insert((v, a), ord);
+        }
+    }
+
+    /// Remove the edge `v.a`, if it exists, together with its ordinal
+    /// and its entry in the reverse index, and return its target.
+    pub(crate) fn drop_edge(&mut self, v: usize, a: Label) -> Option<usize> {
+        self.ords.remove(&(v, a));
+        let vtx = self.vertices.get_mut(v).unwrap();
+        let to = vtx.edges.get(&a).copied()?;
+        vtx.edges.remove(&a);
+        self.unindex_edge(v, a, to);
+        Some(to)
+    }
+
+    /// Get the ordinal of the edge `v.a`, set by [`Sodg::bind_ordered`],
+    /// or zero if it wasn't set.
+    pub(crate) fn ord(&self, v: usize, a: Label) -> usize {
+        self.ords.get(&(v, a)).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn collects_all_edges() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(1, 2, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::from_str("bar").unwrap());
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    assert_eq!(
+        vec![
+            (0, Label::from_str("bar").unwrap(), 2),
+            (0, Label::from_str("foo").unwrap(), 1),
+            (1, Label::from_str("foo").unwrap(), 2),
+        ],
+        g.to_edge_vec()
+    );
+}
+
+#[test]
+fn counts_edges_of_each_vertex() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    for v in 1..5 {
+        g.add(v);
+        g.bind(0, v, Label::Alpha(v));
+    }
+    g.bind(1, 2, Label::Alpha(0));
+    let expected: BTreeMap<usize, usize> = [(0, 4), (1, 1), (2, 0), (3, 0), (4, 0)].into();
+    assert_eq!(expected, g.edge_counts());
+}
+
+#[test]
+fn finds_edges_between_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 1, Label::from_str("bar").unwrap());
+    g.bind(0, 2, Label::from_str("xyz").unwrap());
+    assert_eq!(
+        vec![
+            Label::from_str("bar").unwrap(),
+            Label::from_str("foo").unwrap()
+        ],
+        g.edges_between(0, 1).unwrap()
+    );
+    assert!(g.edges_between(1, 0).unwrap().is_empty());
+    assert!(g.edges_between(0, 3).is_err());
+}
+
+#[test]
+fn removes_edges_by_label() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::from_str("bar").unwrap());
+    g.bind(1, 2, Label::from_str("foo").unwrap());
+    g.bind(2, 0, Label::from_str("xyz").unwrap());
+    let foo = Label::from_str("foo").unwrap();
+    assert_eq!(2, g.retain_edges(|_, a, _| a != foo));
+    assert_eq!(3, g.len());
+    assert_eq!(
+        vec![
+            (0, Label::from_str("bar").unwrap(), 2),
+            (2, Label::from_str("xyz").unwrap(), 0),
+        ],
+        g.to_edge_vec()
+    );
+}
+
+#[test]
+fn renames_edges_by_map() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::from_str("bar").unwrap());
+    g.bind(1, 2, Label::from_str("foo").unwrap());
+    let map = HashMap::from([
+        (
+            Label::from_str("foo").unwrap(),
+            Label::from_str("x").unwrap(),
+        ),
+        (
+            Label::from_str("bar").unwrap(),
+            Label::from_str("y").unwrap(),
+        ),
+    ]);
+    assert_eq!(3, g.rename_attrs(&map).unwrap());
+    assert_eq!(Some(1), g.kid(0, Label::from_str("x").unwrap()));
+    assert_eq!(Some(2), g.kid(0, Label::from_str("y").unwrap()));
+    assert_eq!(Some(2), g.kid(1, Label::from_str("x").unwrap()));
+    assert!(g.kid(0, Label::from_str("foo").unwrap()).is_none());
+}
+
+#[test]
+fn refuses_to_rename_into_duplicate() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 1, Label::Alpha(1));
+    let map = HashMap::from([(Label::Alpha(0), Label::Alpha(1))]);
+    assert!(g.rename_attrs(&map).is_err());
+    assert_eq!(Some(1), g.kid(0, Label::Alpha(0)));
+}
+
+#[test]
+fn removes_dangling_edges() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 2, Label::Alpha(1));
+    g.bind(1, 2, Label::Alpha(0));
+    g.remove(2);
+    assert_eq!(2, g.fix_dangling());
+    assert_eq!(vec![(0, Label::Alpha(0), 1)], g.to_edge_vec());
+    assert_eq!(0, g.fix_dangling());
+}
+
+#[test]
+fn validates_edge_targets() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(1, 2, Label::Alpha(0));
+    assert!(g.validate().is_ok());
+    g.bind(0, 2, Label::Alpha(1));
+    g.remove(2);
+    assert_eq!(
+        "The edge ν0.α1 points to ν2, which is absent",
+        g.validate().unwrap_err().to_string()
+    );
+    g.fix_dangling();
+    assert!(g.validate().is_ok());
+}
+
+#[test]
+fn renames_edges_of_one_source() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..4 {
+        g.add(v);
+    }
+    let foo = Label::from_str("foo").unwrap();
+    let bar = Label::from_str("bar").unwrap();
+    g.bind(0, 1, foo);
+    g.bind(2, 3, foo);
+    g.bind(3, 1, foo);
+    assert_eq!(1, g.rename_attr_where(foo, bar, |v, _| v == 2).unwrap());
+    assert_eq!(vec![(0, foo, 1), (2, bar, 3), (3, foo, 1)], g.to_edge_vec());
+    g.bind(0, 2, bar);
+    assert!(g.rename_attr_where(foo, bar, |_, to| to == 1).is_err());
+    assert_eq!(Some(1), g.kid(3, foo));
+}
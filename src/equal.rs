@@ -0,0 +1,99 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Hex, Persistence, Sodg};
+use itertools::Itertools;
+
+impl<const N: usize> Sodg<N> {
+    /// Compare this graph with another one, using the provided function
+    /// to compare the data in vertices.
+    ///
+    /// The structure is compared exactly: both graphs must have the same
+    /// vertices, with the same IDs, and the same edges between them. Only
+    /// the data is compared by `data_eq`, which is called for each pair
+    /// of vertices that both have data. If only one of them has data,
+    /// the graphs are not equal. For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut a : Sodg<16> = Sodg::empty(256);
+    /// a.add(0);
+    /// a.put(0, &Hex::from_str_bytes("Hello"));
+    /// let mut b : Sodg<16> = Sodg::empty(256);
+    /// b.add(0);
+    /// b.put(0, &Hex::from_str_bytes("HELLO"));
+    /// assert!(!a.equal_with(&b, |x, y| x == y));
+    /// assert!(a.equal_with(&b, |x, y| x.to_utf8().unwrap().to_lowercase() == y.to_utf8().unwrap().to_lowercase()));
+    /// ```
+    #[must_use]
+    pub fn equal_with<const M: usize>(
+        &self,
+        other: &Sodg<M>,
+        data_eq: impl Fn(&Hex, &Hex) -> bool,
+    ) -> bool {
+        let keys = self.keys().into_iter().sorted().collect::<Vec<usize>>();
+        if keys != other.keys().into_iter().sorted().collect::<Vec<usize>>() {
+            return false;
+        }
+        if self.to_edge_vec() != other.to_edge_vec() {
+            return false;
+        }
+        keys.into_iter()
+            .all(|v| match (self.vertices.get(v), other.vertices.get(v)) {
+                (Some(x), Some(y)) => match (
+                    x.persistence == Persistence::Empty,
+                    y.persistence == Persistence::Empty,
+                ) {
+                    (true, true) => true,
+                    (false, false) => data_eq(&x.data, &y.data),
+                    _ => false,
+                },
+                _ => false,
+            })
+    }
+}
+
+#[cfg(test)]
+use crate::Label;
+
+#[test]
+fn compares_data_with_custom_comparator() {
+    let mut a: Sodg<16> = Sodg::empty(256);
+    a.add(0);
+    a.add(1);
+    a.bind(0, 1, Label::Alpha(0));
+    a.put(1, &Hex::from_slice(&[0x01, 0x02]));
+    let mut b: Sodg<16> = Sodg::empty(256);
+    b.add(0);
+    b.add(1);
+    b.bind(0, 1, Label::Alpha(0));
+    b.put(1, &Hex::from_slice(&[0x01, 0x02, 0x00, 0x00]));
+    assert!(!a.equal_with(&b, |x, y| x == y));
+    let trimmed = |h: &Hex| {
+        let mut bytes = h.to_vec();
+        while bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        bytes
+    };
+    assert!(a.equal_with(&b, |x, y| trimmed(x) == trimmed(y)));
+    b.bind(1, 0, Label::Alpha(1));
+    assert!(!a.equal_with(&b, |x, y| trimmed(x) == trimmed(y)));
+}
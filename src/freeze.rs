@@ -0,0 +1,171 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{FrozenSodg, FrozenVertex, Hex, Label, Persistence, Sodg};
+use itertools::Itertools;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+impl<const N: usize> Sodg<N> {
+    /// Make an immutable copy of the graph, which can be shared
+    /// between threads.
+    ///
+    /// Reading data from the copy doesn't submit vertices to
+    /// garbage collection, neither in the copy nor in the original graph.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    #[must_use]
+    pub fn freeze(&self) -> Arc<FrozenSodg> {
+        let vertices = self
+            .keys()
+            .into_iter()
+            .map(|v| {
+                let vtx = self.vertices.get(v).unwrap();
+                (
+                    v,
+                    FrozenVertex {
+                        data: if vtx.persistence == Persistence::Empty {
+                            None
+                        } else {
+                            Some(vtx.data.clone())
+                        },
+                        edges: vtx
+                            .edges
+                            .iter()
+                            .filter(|(_, to)| self.alive(**to))
+                            .map(|(a, to)| (*a, *to))
+                            .sorted()
+                            .collect(),
+                    },
+                )
+            })
+            .collect();
+        Arc::new(FrozenSodg { vertices })
+    }
+}
+
+impl FrozenSodg {
+    /// Get total number of vertices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Is it empty?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Get IDs of all vertices, in ascending order.
+    #[must_use]
+    pub fn keys(&self) -> Vec<usize> {
+        self.vertices.keys().copied().sorted().collect()
+    }
+
+    /// Find a kid of a vertex, by its edge name.
+    ///
+    /// If there is no such vertex or no such edge, `None` is returned.
+    #[must_use]
+    pub fn kid(&self, v: usize, a: Label) -> Option<usize> {
+        self.vertices
+            .get(&v)?
+            .edges
+            .iter()
+            .find(|(k, _)| *k == a)
+            .map(|(_, to)| *to)
+    }
+
+    /// Find all kids of a vertex, sorted by labels.
+    ///
+    /// If there is no such vertex, an empty iterator is returned.
+    pub fn kids(&self, v: usize) -> impl Iterator<Item = &(Label, usize)> + '_ {
+        self.vertices
+            .get(&v)
+            .map(|vtx| vtx.edges.as_slice())
+            .unwrap_or_default()
+            .iter()
+    }
+
+    /// Read the data of a vertex.
+    ///
+    /// If there is no such vertex or it has no data, `None` is returned.
+    #[must_use]
+    pub fn data(&self, v: usize) -> Option<&Hex> {
+        self.vertices.get(&v)?.data.as_ref()
+    }
+
+    /// Walk the graph breadth-first, starting from the vertex `v`,
+    /// and return the IDs of all vertices visited, in the order of visiting.
+    ///
+    /// If there is no such vertex, an empty vector is returned.
+    #[must_use]
+    pub fn bfs(&self, v: usize) -> Vec<usize> {
+        let mut order = vec![];
+        if !self.vertices.contains_key(&v) {
+            return order;
+        }
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert(v);
+        queue.push_back(v);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for (_, to) in self.kids(u) {
+                if seen.insert(*to) {
+                    queue.push_back(*to);
+                }
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[cfg(test)]
+use std::thread;
+
+#[test]
+fn queries_frozen_graph_from_threads() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(1, 2, Label::from_str("bar").unwrap());
+    g.put(2, &Hex::from(42));
+    let f = g.freeze();
+    let first = {
+        let f = Arc::clone(&f);
+        thread::spawn(move || f.kid(1, Label::from_str("bar").unwrap()))
+    };
+    let second = {
+        let f = Arc::clone(&f);
+        thread::spawn(move || (f.bfs(0), f.data(2).unwrap().to_i64().unwrap()))
+    };
+    assert_eq!(Some(2), first.join().unwrap());
+    assert_eq!((vec![0, 1, 2], 42), second.join().unwrap());
+    assert_eq!(3, f.len());
+    assert_eq!(42, g.data(2).unwrap().to_i64().unwrap());
+}
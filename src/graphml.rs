@@ -0,0 +1,138 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Persistence, Sodg};
+use itertools::Itertools;
+
+impl<const N: usize> Sodg<N> {
+    /// Print SODG as a [GraphML](http://graphml.graphdrawing.org/) document.
+    ///
+    /// For example, for this code:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Hex, Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from_str_bytes("hello"));
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// let graphml = g.to_graphml();
+    /// println!("{}", graphml);
+    /// ```
+    ///
+    /// The printout will look like this:
+    ///
+    /// ```xml
+    /// <?xml version="1.0" encoding="UTF-8"?>
+    /// <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+    ///   <key id="data" for="node" attr.name="data" attr.type="string"/>
+    ///   <key id="a" for="edge" attr.name="a" attr.type="string"/>
+    ///   <graph id="sodg" edgedefault="directed">
+    ///     <node id="v0">
+    ///       <data key="data">68-65-6C-6C-6F</data>
+    ///     </node>
+    ///     <node id="v1"/>
+    ///     <edge source="v0" target="v1">
+    ///       <data key="a">foo</data>
+    ///     </edge>
+    ///   </graph>
+    /// </graphml>
+    /// ```
+    #[must_use]
+    pub fn to_graphml(&self) -> String {
+        let mut lines = vec![
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">".to_string(),
+            "  <key id=\"data\" for=\"node\" attr.name=\"data\" attr.type=\"string\"/>".to_string(),
+            "  <key id=\"a\" for=\"edge\" attr.name=\"a\" attr.type=\"string\"/>".to_string(),
+            "  <graph id=\"sodg\" edgedefault=\"directed\">".to_string(),
+        ];
+        let alive = self
+            .vertices
+            .iter()
+            .filter(|(v, _)| self.alive(*v))
+            .sorted_by_key(|(v, _)| *v)
+            .collect::<Vec<_>>();
+        for (v, vtx) in &alive {
+            if vtx.persistence == Persistence::Empty {
+                lines.push(format!("    <node id=\"v{v}\"/>"));
+            } else {
+                lines.push(format!("    <node id=\"v{v}\">"));
+                lines.push(format!(
+                    "      <data key=\"data\">{}</data>",
+                    escape(&vtx.data.print())
+                ));
+                lines.push("    </node>".to_string());
+            }
+        }
+        for (v, vtx) in &alive {
+            for (a, to) in vtx.edges.iter().sorted_by_key(|e| e.0) {
+                if !self.alive(*to) {
+                    continue;
+                }
+                lines.push(format!("    <edge source=\"v{v}\" target=\"v{to}\">"));
+                lines.push(format!(
+                    "      <data key=\"a\">{}</data>",
+                    escape(&a.to_string())
+                ));
+                lines.push("    </edge>".to_string());
+            }
+        }
+        lines.push("  </graph>".to_string());
+        lines.push("</graphml>\n".to_string());
+        lines.join("\n")
+    }
+}
+
+/// Escape special characters of XML.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+use crate::{Hex, Label};
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn prints_simple_graph_to_graphml() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from_str_bytes("hi"));
+    g.add(1);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    let graphml = g.to_graphml();
+    assert!(graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml "));
+    assert!(graphml.contains("<node id=\"v0\">\n      <data key=\"data\">68-69</data>"));
+    assert!(
+        graphml.contains("<edge source=\"v0\" target=\"v1\">\n      <data key=\"a\">foo</data>")
+    );
+}
+
+#[test]
+fn escapes_xml_characters() {
+    assert_eq!("&lt;a&gt; &amp; &quot;b&quot;", escape("<a> & \"b\""));
+}
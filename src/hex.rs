@@ -19,7 +19,7 @@
 // SOFTWARE.
 
 use crate::{Hex, HEX_SIZE};
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 
@@ -98,7 +98,7 @@ impl Hex {
     /// assert_eq!(8, d.len());
     /// ```
     #[must_use]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         match self {
             Self::Vector(x) => x.len(),
             Self::Bytes(_, size) => *size,
@@ -150,6 +150,48 @@ impl Hex {
         }
     }
 
+    /// Create a new [`Hex`] from `i64`, as eight big-endian bytes.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::Hex;
+    /// let d = Hex::from_i64(-1);
+    /// assert_eq!("FF-FF-FF-FF-FF-FF-FF-FF", d.print());
+    /// ```
+    #[must_use]
+    pub fn from_i64(d: i64) -> Self {
+        Self::from(d)
+    }
+
+    /// Create a new [`Hex`] from `f64`, as eight big-endian bytes.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::Hex;
+    /// let d = Hex::from_f64(1.0);
+    /// assert_eq!("3F-F0-00-00-00-00-00-00", d.print());
+    /// ```
+    #[must_use]
+    pub fn from_f64(d: f64) -> Self {
+        Self::from(d)
+    }
+
+    /// Create a new [`Hex`] from `bool`, as a single byte.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::Hex;
+    /// let d = Hex::from_bool(true);
+    /// assert_eq!("01", d.print());
+    /// ```
+    #[must_use]
+    pub fn from_bool(d: bool) -> Self {
+        Self::from(d)
+    }
+
     /// Create a new [`Hex`] from the bytes composing `&str`.
     ///
     /// For example:
@@ -174,7 +216,7 @@ impl Hex {
     /// assert_eq!(true, d.is_empty());
     /// ```
     #[must_use]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
@@ -185,11 +227,20 @@ impl Hex {
     /// ```
     /// use sodg::Hex;
     /// let d = Hex::from_vec([0x01].to_vec());
-    /// assert_eq!(true, d.to_bool());
+    /// assert_eq!(true, d.to_bool().unwrap());
     /// ```
-    #[must_use]
-    pub fn to_bool(&self) -> bool {
-        self.bytes()[0] == 0x01
+    ///
+    /// # Errors
+    ///
+    /// If there is not exactly one byte, an error will be returned.
+    pub fn to_bool(&self) -> Result<bool> {
+        match self.bytes() {
+            [b] => Ok(*b == 0x01),
+            bytes => Err(anyhow!(
+                "There must be exactly one byte to make BOOL, while there are {}",
+                bytes.len()
+            )),
+        }
     }
 
     /// Turn it into `i64`.
@@ -440,12 +491,26 @@ impl FromStr for Hex {
     /// assert_eq!(Hex::empty(), d2);
     /// ```
     ///
+    /// Spaces and line breaks are ignored, like in [`crate::Script`]:
+    ///
+    /// ```
+    /// use sodg::Hex;
+    /// use std::str::FromStr;
+    /// let d: Hex = Hex::from_str("CA-FE\n BA BE").unwrap();
+    /// assert_eq!("CA-FE-BA-BE", d.print());
+    /// ```
+    ///
     /// # Errors
     ///
     /// If it's impossible to convert from a String, an error will be returned.
     fn from_str(hex: &str) -> std::result::Result<Self, Self::Err> {
-        let s = hex.replace('-', "");
-        Ok(Self::from_vec(hex::decode(s)?))
+        let s: String = hex
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .collect();
+        Ok(Self::from_vec(hex::decode(s).with_context(|| {
+            format!("Can't parse '{hex}' as hex data")
+        })?))
     }
 }
 
@@ -461,7 +526,7 @@ fn simple_int() {
 fn simple_bool() {
     let b = true;
     let d = Hex::from(b);
-    assert_eq!(b, d.to_bool());
+    assert_eq!(b, d.to_bool().unwrap());
     assert_eq!("01", d.print());
 }
 
@@ -621,3 +686,40 @@ fn concatenates_from_hex_str() {
     let res = a.concat(&b).concat(&c);
     assert_eq!(24, res.len());
 }
+
+#[test]
+fn round_trips_typed_numbers() {
+    for i in [42, -42, 0, i64::MAX, i64::MIN] {
+        assert_eq!(i, Hex::from_i64(i).to_i64().unwrap());
+    }
+    for f in [2.5, -0.001, 0.0] {
+        assert!((f - Hex::from_f64(f).to_f64().unwrap()).abs() < f64::EPSILON);
+    }
+    assert!(Hex::from_f64(f64::NAN).to_f64().unwrap().is_nan());
+    assert!(Hex::from_bool(true).to_bool().unwrap());
+    assert!(!Hex::from_bool(false).to_bool().unwrap());
+}
+
+#[test]
+fn refuses_typed_numbers_of_wrong_width() {
+    assert!(Hex::from_bool(true).to_i64().is_err());
+    assert!(Hex::from_slice(&[1, 2, 3]).to_f64().is_err());
+    assert!(Hex::from_i64(1).to_bool().is_err());
+    assert!(Hex::empty().to_bool().is_err());
+}
+
+#[test]
+fn prints_and_parses_back() {
+    for bytes in [
+        vec![],
+        vec![0x00],
+        vec![0xCA, 0xFE],
+        vec![0xDE, 0xAD, 0xBE, 0xEF, 0x20, 0x22, 0x01, 0x7F],
+        (0..=255).collect::<Vec<u8>>(),
+    ] {
+        let h = Hex::from_vec(bytes);
+        assert_eq!(h, Hex::from_str(&format!("{h}")).unwrap());
+    }
+    let msg = format!("{:#}", Hex::from_str("CA-FX").unwrap_err());
+    assert!(msg.contains("Can't parse 'CA-FX' as hex data"), "{msg}");
+}
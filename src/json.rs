@@ -0,0 +1,400 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Hex, Label, Persistence, Sodg};
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+impl<const N: usize> Sodg<N> {
+    /// Print the graph as a nested JSON, starting from the vertex `root`.
+    ///
+    /// Each vertex becomes an object with its ID, its data (or `null`),
+    /// and its kids, keyed by the labels of the edges. A vertex that
+    /// was already printed is not printed again, but is referenced
+    /// by `$ref` with its ID. For example, for this code:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Hex, Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// g.put(1, &Hex::from(42));
+    /// g.bind(1, 0, Label::from_str("up").unwrap());
+    /// let json = g.to_nested_json(0).unwrap();
+    /// println!("{}", json);
+    /// ```
+    ///
+    /// The printout will look like this:
+    ///
+    /// ```json
+    /// {"id":0,"data":null,"kids":{"foo":{"id":1,"data":"00-00-00-00-00-00-00-2A","kids":{"up":{"$ref":0}}}}}
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `root` is absent, an `Err` will be returned.
+    pub fn to_nested_json(&self, root: usize) -> Result<String> {
+        if !self.alive(root) {
+            return Err(anyhow!("Can't find ν{root}"));
+        }
+        let mut seen = HashSet::new();
+        let mut json = String::new();
+        self.nested_json_rec(root, &mut seen, &mut json)?;
+        Ok(json)
+    }
+
+    /// Print the entire graph as a JSON object, keyed by the IDs of vertices.
+    ///
+    /// Each vertex becomes an object with its edges, keyed by their labels,
+    /// and its data in hexadecimal format, if there is any. Vertices are
+    /// sorted by their IDs and edges are sorted by their labels, which makes
+    /// the output stable. For example, for this code:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Hex, Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// g.put(1, &Hex::from(42));
+    /// let json = g.to_json().unwrap();
+    /// println!("{}", json);
+    /// ```
+    ///
+    /// The printout will look like this:
+    ///
+    /// ```json
+    /// {"0":{"edges":{"foo":1}},"1":{"edges":{},"data":"00-00-00-00-00-00-00-2A"}}
+    /// ```
+    ///
+    /// Edges pointing to absent vertices are not printed. The graph
+    /// may be restored from this JSON by [`Sodg::from_json`].
+    ///
+    /// # Errors
+    ///
+    /// If it's impossible to print the JSON, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn to_json(&self) -> Result<String> {
+        let mut json = String::from("{");
+        for (i, v) in self.keys().into_iter().sorted().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(json, "\"{v}\":{{\"edges\":{{")?;
+            let kids = self
+                .kids(v)
+                .filter(|(_, to)| self.alive(**to))
+                .sorted_by_key(|(a, _)| **a);
+            for (j, (a, to)) in kids.enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+                json.push('"');
+                escape(&a.to_string(), &mut json)?;
+                write!(json, "\":{to}")?;
+            }
+            json.push('}');
+            let vtx = self.vertices.get(v).unwrap();
+            if vtx.persistence != Persistence::Empty {
+                write!(json, ",\"data\":\"{}\"", vtx.data)?;
+            }
+            json.push('}');
+        }
+        json.push('}');
+        Ok(json)
+    }
+
+    /// Make a new graph from the JSON previously printed by [`Sodg::to_json`].
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let g : Sodg<16> = Sodg::from_json(
+    ///     "{\"0\":{\"edges\":{\"foo\":1}},\"1\":{\"edges\":{},\"data\":\"CA-FE\"}}"
+    /// ).unwrap();
+    /// assert_eq!(1, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    /// ```
+    ///
+    /// The capacity of the graph is just enough to fit the largest ID.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON is broken, a vertex has more than `N` edges, or an edge
+    /// points to a vertex which is not in the JSON, an `Err` will be returned.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut r = Reader {
+            chars: json.chars().peekable(),
+        };
+        let mut vertices: BTreeMap<usize, Option<Hex>> = BTreeMap::new();
+        let mut edges = vec![];
+        r.each(|r, key| {
+            let v = usize::from_str(&key).with_context(|| format!("Wrong vertex ID '{key}'"))?;
+            let mut data = None;
+            let mut total = 0;
+            r.each(|r, field| {
+                match field.as_str() {
+                    "edges" => r.each(|r, a| {
+                        total += 1;
+                        if total > N {
+                            return Err(anyhow!("There are more than {N} edges in ν{v}"));
+                        }
+                        edges.push((v, Label::from_str(&a)?, r.number()?));
+                        Ok(())
+                    })?,
+                    "data" => data = Some(Hex::from_str(&r.string()?)?),
+                    _ => return Err(anyhow!("Unknown field '{field}' in ν{v}")),
+                }
+                Ok(())
+            })?;
+            vertices.insert(v, data);
+            Ok(())
+        })?;
+        r.skip_spaces();
+        if let Some(c) = r.chars.next() {
+            return Err(anyhow!("Unexpected '{c}' after the end of JSON"));
+        }
+        let mut g = Self::empty(vertices.keys().max().map_or(0, |v| v + 1));
+        for v in vertices.keys() {
+            g.add(*v);
+        }
+        for (v, a, to) in edges {
+            if !vertices.contains_key(&to) {
+                return Err(anyhow!("The edge ν{v}.{a} points to absent ν{to}"));
+            }
+            g.bind(v, to, a);
+        }
+        for (v, data) in vertices {
+            if let Some(d) = data {
+                g.put(v, &d);
+            }
+        }
+        Ok(g)
+    }
+
+    fn nested_json_rec(
+        &self,
+        v: usize,
+        seen: &mut HashSet<usize>,
+        json: &mut String,
+    ) -> std::fmt::Result {
+        if !seen.insert(v) {
+            return write!(json, "{{\"$ref\":{v}}}");
+        }
+        let vtx = self.vertices.get(v).unwrap();
+        write!(json, "{{\"id\":{v},\"data\":")?;
+        if vtx.persistence == Persistence::Empty {
+            json.push_str("null");
+        } else {
+            write!(json, "\"{}\"", vtx.data)?;
+        }
+        json.push_str(",\"kids\":{");
+        let kids = vtx
+            .edges
+            .iter()
+            .filter(|(_, to)| self.alive(**to))
+            .sorted_by_key(|(a, _)| **a);
+        for (i, (a, to)) in kids.enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push('"');
+            escape(&a.to_string(), json)?;
+            json.push_str("\":");
+            self.nested_json_rec(*to, seen, json)?;
+        }
+        json.push_str("}}");
+        Ok(())
+    }
+}
+
+/// Escape a string to be printed inside double quotes in JSON.
+fn escape(s: &str, out: &mut String) -> std::fmt::Result {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.push(c),
+        }
+    }
+    Ok(())
+}
+
+/// A reader of JSON, which understands only objects, strings,
+/// and non-negative integers, enough for [`Sodg::from_json`].
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Reader<'_> {
+    fn skip_spaces(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        self.skip_spaces();
+        match self.chars.next() {
+            Some(n) if n == c => Ok(()),
+            Some(n) => Err(anyhow!("'{c}' is expected, while '{n}' found")),
+            None => Err(anyhow!("'{c}' is expected, while JSON is over")),
+        }
+    }
+
+    /// Read an object, calling `f` for each of its keys, to read the value.
+    fn each(&mut self, mut f: impl FnMut(&mut Self, String) -> Result<()>) -> Result<()> {
+        self.expect('{')?;
+        self.skip_spaces();
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(());
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            f(self, key)?;
+            self.skip_spaces();
+            match self.chars.next() {
+                Some(',') => {}
+                Some('}') => return Ok(()),
+                _ => return Err(anyhow!("Either ',' or '}}' is expected")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(out),
+                Some('\\') => match self.chars.next() {
+                    Some('u') => {
+                        let code: String = self.chars.by_ref().take(4).collect();
+                        let c = u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .with_context(|| format!("Wrong escape '\\u{code}'"))?;
+                        out.push(c);
+                    }
+                    Some(c) if c == '"' || c == '\\' || c == '/' => out.push(c),
+                    _ => return Err(anyhow!("Broken escaping in '{out}'")),
+                },
+                Some(c) => out.push(c),
+                None => return Err(anyhow!("The string '{out}' is not closed")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<usize> {
+        self.skip_spaces();
+        let mut digits = String::new();
+        while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+            digits.push(c);
+        }
+        usize::from_str(&digits).with_context(|| format!("A number is expected, not '{digits}'"))
+    }
+}
+
+#[test]
+fn prints_tree_as_nested_json() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.add(2);
+    g.bind(0, 2, Label::from_str("bar").unwrap());
+    g.put(2, &Hex::from_str_bytes("hi"));
+    g.bind(1, 0, Label::from_str("back").unwrap());
+    assert_eq!(
+        "{\"id\":0,\"data\":null,\"kids\":{\
+        \"bar\":{\"id\":2,\"data\":\"68-69\",\"kids\":{}},\
+        \"foo\":{\"id\":1,\"data\":null,\"kids\":{\"back\":{\"$ref\":0}}}}}",
+        g.to_nested_json(0).unwrap()
+    );
+}
+
+#[test]
+fn escapes_quotes_in_json() {
+    let mut out = String::new();
+    escape("a\"b\\c\n", &mut out).unwrap();
+    assert_eq!("a\\\"b\\\\c\\u000a", out);
+}
+
+#[test]
+fn restores_graph_from_json() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in [0, 1, 2, 5] {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::Alpha(0));
+    g.bind(2, 0, Label::Greek('ρ'));
+    g.bind(5, 5, Label::from_str("self").unwrap());
+    g.put(1, &Hex::from_str_bytes("hello"));
+    g.put(5, &Hex::empty());
+    let json = g.to_json().unwrap();
+    assert_eq!(
+        "{\"0\":{\"edges\":{\"α0\":2,\"foo\":1}},\
+        \"1\":{\"edges\":{},\"data\":\"68-65-6C-6C-6F\"},\
+        \"2\":{\"edges\":{\"ρ\":0}},\
+        \"5\":{\"edges\":{\"self\":5},\"data\":\"--\"}}",
+        json
+    );
+    let back: Sodg<16> = Sodg::from_json(&json).unwrap();
+    assert_eq!(json, back.to_json().unwrap());
+    assert_eq!(
+        g.keys().into_iter().sorted().collect::<Vec<_>>(),
+        back.keys().into_iter().sorted().collect::<Vec<_>>()
+    );
+    assert_eq!(g.to_edge_vec(), back.to_edge_vec());
+    for v in g.keys() {
+        assert_eq!(g.has_data(v), back.has_data(v), "ν{v}");
+        assert_eq!(g.bytes_of(v).unwrap(), back.bytes_of(v).unwrap(), "ν{v}");
+    }
+}
+
+#[test]
+fn rejects_broken_json() {
+    for json in [
+        "",
+        "{\"0\":{\"edges\":{\"foo\":1}}}",
+        "{\"x\":{\"edges\":{}}}",
+        "{\"0\":{\"edges\":{}}",
+        "{\"0\":{\"size\":1}}",
+        "{} {}",
+    ] {
+        assert!(Sodg::<16>::from_json(json).is_err(), "{json}");
+    }
+    assert!(Sodg::<16>::from_json(" { } ").unwrap().is_empty());
+    let wide = "{\"0\":{\"edges\":{\"a\":0,\"b\":0,\"c\":0}}}";
+    assert!(Sodg::<3>::from_json(wide).is_ok());
+    assert!(Sodg::<2>::from_json(wide).is_err());
+}
@@ -31,7 +31,7 @@ impl FromStr for Label {
         Ok(if s.starts_with('α') {
             let tail: String = s.chars().skip(1).collect::<Vec<_>>().into_iter().collect();
             Self::Alpha(tail.parse::<usize>()?)
-        } else if s.len() == 1 {
+        } else if s.chars().count() == 1 {
             Self::Greek(s.chars().next().unwrap())
         } else {
             let v: Vec<char> = s.chars().collect();
@@ -75,3 +75,18 @@ fn parses_and_prints(#[case] txt: &str) {
     let l = Label::from_str(txt).unwrap();
     assert_eq!(txt, l.to_string());
 }
+
+#[test]
+fn parses_greek_letter() {
+    assert_eq!(Label::Greek('ρ'), Label::from_str("ρ").unwrap());
+    assert_eq!(Label::Greek('x'), Label::from_str("x").unwrap());
+}
+
+#[rstest]
+#[case(Label::Greek('ρ'))]
+#[case(Label::Greek('φ'))]
+#[case(Label::Greek('x'))]
+#[case(Label::Alpha(0))]
+fn prints_and_parses_back(#[case] label: Label) {
+    assert_eq!(label, Label::from_str(&label.to_string()).unwrap());
+}
@@ -44,23 +44,42 @@
 #![allow(clippy::multiple_crate_versions)]
 
 mod clone;
+mod components;
 mod ctors;
+mod cycles;
+mod data;
 mod debug;
 mod dot;
+mod edges;
+mod equal;
+mod freeze;
+mod graphml;
 mod hex;
 mod inspect;
+mod json;
 mod label;
 mod merge;
 mod misc;
 mod next;
 mod ops;
+mod ordered;
+mod parents;
+mod path;
+mod query;
+mod remove;
+mod reverse;
 mod script;
 mod serialization;
+mod sexp;
 mod slice;
+mod snapshot;
+mod view;
 mod xml;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
 
 const HEX_SIZE: usize = 8;
 const MAX_BRANCHES: usize = 16;
@@ -112,11 +131,28 @@ pub enum Label {
 /// In the script you can use "variables", similar to `$ν1` used
 /// in the text above. They will be replaced by autogenerated numbers
 /// during the deployment of this script to a [`Sodg`].
+#[derive(Clone)]
 pub struct Script {
     /// The text of it.
     txt: String,
     /// The vars dynamically discovered.
     vars: HashMap<String, usize>,
+    /// The directory where relative paths are resolved.
+    base: PathBuf,
+    /// The maximum length of a single command, in bytes.
+    max_len: usize,
+    /// The renames of labels, registered by `ATTRMAP`.
+    attrs: HashMap<Label, Label>,
+    /// The vertices found or created for `@"..."` references, by their data.
+    interned: HashMap<String, usize>,
+    /// The default byte order of typed numbers, set by `ENCODING`.
+    little_endian: bool,
+    /// The fragments of scripts, registered by [`Script::register`].
+    fragments: HashMap<String, String>,
+    /// The names of fragments being deployed at the moment, by `USE`.
+    using: Vec<String>,
+    /// Whether files must not be written, like in [`Script::check_against`].
+    dry: bool,
 }
 
 /// A struct that represents a Surging Object Di-Graph (SODG).
@@ -146,6 +182,58 @@ pub struct Sodg<const N: usize> {
     /// This is the next ID of a vertex to be returned by the [`Sodg::next_v`] function.
     #[serde(skip_serializing, skip_deserializing)]
     next_v: usize,
+    /// The reverse index of edges, unless disabled by [`Sodg::enable_reverse_index`]:
+    /// a target vertex is mapped to its sources and labels.
+    #[serde(skip_serializing, skip_deserializing)]
+    reverse: Option<HashMap<usize, Vec<(usize, Label)>>>,
+    /// The ordinals of edges, set by [`Sodg::bind_ordered`]: edges
+    /// that are not here have the ordinal of zero.
+    ords: HashMap<(usize, Label), usize>,
+    /// The callback notified about read operations, set by [`Sodg::set_query_logger`].
+    #[serde(skip_serializing, skip_deserializing)]
+    logger: Option<Mutex<QueryLogger>>,
+}
+
+/// A callback that gets a short description of each read operation
+/// on a [`Sodg`], see [`Sodg::set_query_logger`].
+pub type QueryLogger = Box<dyn FnMut(&str) + Send>;
+
+/// A saved state of a [`Sodg`], made by [`Sodg::snapshot`] and
+/// later given back to [`Sodg::restore`].
+pub struct Snapshot<const N: usize> {
+    g: Sodg<N>,
+}
+
+/// An immutable copy of a [`Sodg`], made by [`Sodg::freeze`].
+///
+/// It can be shared between threads and queried concurrently,
+/// without any locks:
+///
+/// ```
+/// use std::str::FromStr;
+/// use sodg::{Label, Sodg};
+/// let mut g : Sodg<16> = Sodg::empty(256);
+/// g.add(0);
+/// g.add(1);
+/// g.bind(0, 1, Label::from_str("foo").unwrap());
+/// let f = g.freeze();
+/// let t = std::thread::spawn(move || f.kid(0, Label::from_str("foo").unwrap()));
+/// assert_eq!(Some(1), t.join().unwrap());
+/// ```
+pub struct FrozenSodg {
+    vertices: HashMap<usize, FrozenVertex>,
+}
+
+struct FrozenVertex {
+    data: Option<Hex>,
+    edges: Vec<(Label, usize)>,
+}
+
+/// A read-only view of a vertex, returned by [`Sodg::iter_vertices`].
+pub struct VertexView<'a> {
+    id: usize,
+    data: Option<&'a Hex>,
+    degree: usize,
 }
 
 #[derive(PartialEq, Serialize, Deserialize, Clone)]
@@ -170,14 +258,50 @@ struct Vertex<const N: usize> {
 use simple_logger::SimpleLogger;
 
 #[cfg(test)]
-use log::LevelFilter;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// The messages of level `INFO` and above, logged by all tests so far.
+#[cfg(test)]
+static LOGGED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// A logger for tests, which prints everything, like [`SimpleLogger`],
+/// and also remembers important messages in [`LOGGED`].
+#[cfg(test)]
+struct TestLogger {
+    inner: SimpleLogger,
+}
+
+#[cfg(test)]
+impl Log for TestLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= Level::Info {
+            LOGGED.lock().unwrap().push(record.args().to_string());
+        }
+        self.inner.log(record);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Check whether a message containing `needle` was logged
+/// at `INFO` level or above by any test.
+#[cfg(test)]
+fn logged(needle: &str) -> bool {
+    LOGGED.lock().unwrap().iter().any(|m| m.contains(needle))
+}
 
 #[cfg(test)]
 #[ctor::ctor]
 fn init() {
-    SimpleLogger::new()
+    let inner = SimpleLogger::new()
         .without_timestamps()
-        .with_level(LevelFilter::Trace)
-        .init()
-        .unwrap();
+        .with_level(LevelFilter::Trace);
+    log::set_logger(Box::leak(Box::new(TestLogger { inner }))).unwrap();
+    log::set_max_level(LevelFilter::Trace);
 }
@@ -18,10 +18,11 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::{Label, Persistence, Sodg};
+use crate::{Label, Persistence, Sodg, BRANCH_NONE};
 use anyhow::{anyhow, Result};
 use log::debug;
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
 impl<const N: usize> Sodg<N> {
     /// Merge another graph into the current one.
@@ -117,15 +118,349 @@ impl<const N: usize> Sodg<N> {
         Ok(())
     }
 
-    fn join(&mut self, left: usize, right: usize) {
+    /// Contract two vertices into one: all edges of the vertex `drop`,
+    /// both departing and arriving, are moved to the vertex `keep`, and
+    /// then `drop` is removed.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// g.bind(0, 2, Label::Alpha(0));
+    /// g.contract(1, 2).unwrap();
+    /// assert_eq!(1, g.kid(0, Label::Alpha(0)).unwrap());
+    /// ```
+    ///
+    /// If `keep` already has a departing edge with the same label, the edge
+    /// of `drop` is skipped. Edges between `keep` and `drop` are
+    /// removed. If `keep` has no data, it gets the data of `drop`;
+    /// otherwise the data of `drop` is lost.
+    ///
+    /// # Errors
+    ///
+    /// If either vertex is absent, they are the same vertex, or `keep`
+    /// would get more edges than `N`, an `Err` will be returned
+    /// and nothing will be changed.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn contract(&mut self, keep: usize, drop: usize) -> Result<()> {
+        for v in [keep, drop] {
+            if !self.alive(v) {
+                return Err(anyhow!("Can't find ν{v}"));
+            }
+        }
+        if keep == drop {
+            return Err(anyhow!("Can't contract ν{keep} into itself"));
+        }
+        let staying: HashSet<Label> = self
+            .kids(keep)
+            .filter(|(_, to)| **to != drop)
+            .map(|(a, _)| *a)
+            .collect();
+        let moving = self
+            .kids(drop)
+            .filter(|(a, to)| **to != keep && **to != drop && !staying.contains(*a))
+            .count();
+        if staying.len() + moving > N {
+            return Err(anyhow!(
+                "Can't contract ν{drop} into ν{keep}, there would be more than {N} edges"
+            ));
+        }
         for v in self.keys() {
-            let mut nv = self.vertices.get(v).unwrap().clone();
-            for e in &self.vertices.get_mut(v).unwrap().edges {
-                if *e.1 == right {
-                    nv.edges.insert(*e.0, left);
+            if v == drop {
+                continue;
+            }
+            let arriving: Vec<Label> = self
+                .kids(v)
+                .filter(|(_, to)| **to == drop)
+                .map(|(a, _)| *a)
+                .collect();
+            for a in arriving {
+                if v == keep {
+                    self.drop_edge(v, a);
+                } else {
+                    self.rebind(v, a, keep)?;
+                }
+            }
+        }
+        let departing: Vec<(Label, usize)> = self.kids(drop).map(|(a, to)| (*a, *to)).collect();
+        for (a, to) in departing {
+            if to != keep && to != drop && self.kid(keep, a).is_none() {
+                self.bind_ordered(keep, to, a, self.ord(drop, a))?;
+            }
+        }
+        let vtx = self.vertices.get(drop).unwrap();
+        if vtx.persistence != Persistence::Empty
+            && self.vertices.get(keep).unwrap().persistence == Persistence::Empty
+        {
+            let d = vtx.data.clone();
+            self.put(keep, &d);
+        }
+        self.remove(drop);
+        self.reindex();
+        Ok(())
+    }
+
+    /// Remove all pass-through vertices, which have no data, exactly one
+    /// arriving edge and exactly one departing edge, and return how many
+    /// of them were removed.
+    ///
+    /// The two edges of such a vertex are replaced with one edge, labeled
+    /// by both labels joined with a slash, which [`Sodg::find`] doesn't split
+    /// on. For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// g.bind(0, 1, Label::from_str("a").unwrap());
+    /// g.bind(1, 2, Label::from_str("b").unwrap());
+    /// assert_eq!(1, g.collapse_chains());
+    /// assert_eq!(2, g.find(0, "a/b").unwrap());
+    /// ```
+    ///
+    /// A vertex is not removed if the joined label can't be a [`Label`],
+    /// or its parent already has an edge with this label. The joined label
+    /// must fit into eight characters and must not start with `α`, so
+    /// an arriving edge like `α0` is never joined.
+    ///
+    /// The reverse index, see [`Sodg::enable_reverse_index`], is used to find
+    /// parents; if it's turned off, it's built for the time of the call.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn collapse_chains(&mut self) -> usize {
+        let indexed = self.reverse.is_some();
+        if !indexed {
+            self.enable_reverse_index(true);
+        }
+        let mut total = 0;
+        loop {
+            let before = total;
+            for u in self.keys() {
+                if let Some((parent, a, kid, joined)) = self.pass_through(u) {
+                    self.drop_edge(parent, a);
+                    self.bind(parent, kid, joined);
+                    self.remove(u);
+                    total += 1;
+                }
+            }
+            if total == before {
+                break;
+            }
+        }
+        if !indexed {
+            self.enable_reverse_index(false);
+        }
+        total
+    }
+
+    /// If the vertex `u` is a pass-through one, return its parent,
+    /// the label of the arriving edge, its only kid, and the joined label.
+    fn pass_through(&self, u: usize) -> Option<(usize, Label, usize, Label)> {
+        if !self.alive(u) || self.has_data(u) {
+            return None;
+        }
+        let mut kids = self.kids(u);
+        let (b, kid) = kids.next()?;
+        if kids.next().is_some() || *kid == u || !self.alive(*kid) {
+            return None;
+        }
+        let parents = self.parents(u).ok()?;
+        let [(parent, a)] = parents.as_slice() else {
+            return None;
+        };
+        if *parent == u {
+            return None;
+        }
+        let joined = Label::from_str(&format!("{a}/{b}")).ok()?;
+        if self.kid(*parent, joined).is_some() {
+            return None;
+        }
+        Some((*parent, *a, *kid, joined))
+    }
+
+    /// Copy all vertices of another graph into the current one, giving
+    /// them fresh IDs, together with their edges and data.
+    ///
+    /// Unlike [`Sodg::merge`], no vertices are matched: every vertex
+    /// of `g` gets a new ID from [`Sodg::next_id`], and the edges
+    /// between them are rebound to the new IDs. The returned map
+    /// tells the new ID for each ID of `g`. For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// let mut extra : Sodg<16> = Sodg::empty(256);
+    /// extra.add(0);
+    /// extra.add(1);
+    /// extra.bind(0, 1, Label::Alpha(0));
+    /// let ids = g.absorb(&extra).unwrap();
+    /// assert_eq!(3, g.len());
+    /// assert_eq!(ids[&1], g.kid(ids[&0], Label::Alpha(0)).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If [`Sodg::next_id`] can't give enough IDs for all vertices of `g`,
+    /// an `Err` will be returned and nothing will be copied. Mind that
+    /// the IDs below the last one given by it are not reused.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn absorb(&mut self, g: &Self) -> Result<HashMap<usize, usize>> {
+        let keys = g.keys();
+        let room = self
+            .vertices
+            .iter()
+            .filter(|(v, vtx)| vtx.branch == BRANCH_NONE && *v >= self.next_v)
+            .take(keys.len())
+            .count();
+        if keys.len() > room {
+            return Err(anyhow!(
+                "Can't absorb {} vertices, there are {room} free IDs only",
+                keys.len()
+            ));
+        }
+        let mut ids = HashMap::new();
+        for v in &keys {
+            let id = self.next_id();
+            self.add(id);
+            ids.insert(*v, id);
+        }
+        for v in keys {
+            let vtx = g.vertices.get(v).unwrap();
+            for (a, to) in &vtx.edges {
+                if let Some(t) = ids.get(to) {
+                    self.bind(ids[&v], *t, *a);
+                }
+            }
+            if vtx.persistence != Persistence::Empty {
+                self.put(ids[&v], &vtx.data);
+            }
+        }
+        debug!("Absorbed {} vertices into SODG", ids.len());
+        Ok(ids)
+    }
+
+    /// Apply another graph on top of the current one, as a patch,
+    /// matching the vertices by their IDs.
+    ///
+    /// Vertices of `g` absent in the current graph are added. The data
+    /// of a vertex is replaced, if the vertex in `g` has data. The edges
+    /// of `g` are bound, replacing the edges with the same labels. For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.put(0, &Hex::from(1));
+    /// let mut patch : Sodg<16> = Sodg::empty(256);
+    /// patch.add(0);
+    /// patch.put(0, &Hex::from(2));
+    /// patch.add(1);
+    /// patch.bind(0, 1, Label::Alpha(0));
+    /// g.overlay(&patch).unwrap();
+    /// assert_eq!(2, g.data(0).unwrap().to_i64().unwrap());
+    /// assert_eq!(1, g.kid(0, Label::Alpha(0)).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a vertex of `g` doesn't fit into the current graph, an `Err`
+    /// will be returned and nothing will be changed.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn overlay(&mut self, g: &Self) -> Result<()> {
+        let keys = g.keys();
+        if let Some(v) = keys.iter().find(|v| **v >= self.vertices.capacity()) {
+            return Err(anyhow!(
+                "Can't overlay ν{v}, the capacity is {}",
+                self.vertices.capacity()
+            ));
+        }
+        for v in &keys {
+            if !self.alive(*v) {
+                self.add(*v);
+            }
+        }
+        for v in &keys {
+            for (a, to) in g.kids(*v) {
+                if g.alive(*to) {
+                    self.bind(*v, *to, *a);
                 }
             }
-            self.vertices.insert(v, nv);
+        }
+        for v in keys {
+            if g.has_data(v) {
+                self.put(v, &g.vertices.get(v).unwrap().data);
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy all vertices of another graph into the current one, shifting
+    /// their IDs by `offset`, together with their edges and data.
+    ///
+    /// Vertices already present under the shifted IDs are reused, while
+    /// their edges with the same labels are overwritten.
+    ///
+    /// If any shifted ID doesn't fit into the capacity, an `Err` is
+    /// returned and nothing is copied.
+    pub(crate) fn inject(&mut self, g: &Self, offset: usize) -> Result<()> {
+        let cap = self.vertices.capacity();
+        if let Some(v) = g
+            .keys()
+            .into_iter()
+            .find(|v| v.checked_add(offset).is_none_or(|id| id >= cap))
+        {
+            return Err(anyhow!(
+                "The ν{v} with offset {offset} doesn't fit into capacity {cap}"
+            ));
+        }
+        for v in g.keys() {
+            if !self.alive(v + offset) {
+                self.add(v + offset);
+            }
+        }
+        for v in g.keys() {
+            let vtx = g.vertices.get(v).unwrap();
+            for (a, to) in &vtx.edges {
+                if g.alive(*to) {
+                    self.bind(v + offset, to + offset, *a);
+                }
+            }
+            if vtx.persistence != Persistence::Empty {
+                self.put(v + offset, &vtx.data);
+            }
+        }
+        Ok(())
+    }
+
+    fn join(&mut self, left: usize, right: usize) {
+        for v in self.keys() {
+            let arriving: Vec<Label> = self
+                .kids(v)
+                .filter(|(_, to)| **to == right)
+                .map(|(a, _)| *a)
+                .collect();
+            for a in arriving {
+                self.put_edge(v, a, left, self.ord(v, a));
+            }
         }
         let kids = self
             .kids(right)
@@ -139,13 +474,12 @@ impl<const N: usize> Sodg<N> {
             );
             self.bind(left, e.1, e.0);
         }
+        self.ords.retain(|(v, _), _| *v != right);
         self.vertices.remove(right);
+        self.reindex();
     }
 }
 
-#[cfg(test)]
-use std::str::FromStr;
-
 #[test]
 fn merges_two_graphs() {
     let mut g: Sodg<16> = Sodg::empty(256);
@@ -397,3 +731,160 @@ fn two_big_graphs() {
     g.merge(&extra, 0, 0).unwrap();
     assert_eq!(4, g.len());
 }
+
+#[test]
+fn contracts_two_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..5 {
+        g.add(v);
+    }
+    g.bind(0, 2, Label::from_str("a").unwrap());
+    g.bind(1, 3, Label::from_str("x").unwrap());
+    g.bind(2, 3, Label::from_str("x").unwrap());
+    g.bind(2, 4, Label::from_str("y").unwrap());
+    g.bind(1, 2, Label::from_str("z").unwrap());
+    g.put(2, &Hex::from(42));
+    g.contract(1, 2).unwrap();
+    assert_eq!(4, g.len());
+    assert_eq!(1, g.kid(0, Label::from_str("a").unwrap()).unwrap());
+    assert_eq!(3, g.kid(1, Label::from_str("x").unwrap()).unwrap());
+    assert_eq!(4, g.kid(1, Label::from_str("y").unwrap()).unwrap());
+    assert!(g.kid(1, Label::from_str("z").unwrap()).is_none());
+    assert_eq!(42, g.data(1).unwrap().to_i64().unwrap());
+    assert!(g.contract(1, 2).is_err());
+}
+
+#[test]
+fn collapses_linear_chain() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..5 {
+        g.add(v);
+    }
+    for (v, a) in ["a", "b", "c", "d"].into_iter().enumerate() {
+        g.bind(v, v + 1, Label::from_str(a).unwrap());
+    }
+    g.put(4, &Hex::from(42));
+    assert_eq!(3, g.collapse_chains());
+    assert_eq!(vec![0, 4], {
+        let mut keys = g.keys();
+        keys.sort_unstable();
+        keys
+    });
+    assert_eq!(
+        vec![(0, Label::from_str("a/b/c/d").unwrap(), 4)],
+        g.to_edge_vec()
+    );
+    assert_eq!(0, g.collapse_chains());
+    assert_eq!(4, g.find(0, "a/b/c/d").unwrap());
+}
+
+#[test]
+fn collapses_chain_under_full_parent() {
+    let mut g: Sodg<2> = Sodg::empty(16);
+    for v in 0..5 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::from_str("a").unwrap());
+    g.bind(0, 3, Label::Alpha(0));
+    g.bind(1, 2, Label::from_str("b").unwrap());
+    g.bind(3, 4, Label::Alpha(1));
+    g.put(2, &Hex::from(1));
+    g.put(4, &Hex::from(2));
+    assert_eq!(1, g.collapse_chains());
+    assert_eq!(2, g.find(0, "a/b").unwrap());
+    assert_eq!(4, g.find(0, "α0.α1").unwrap());
+}
+
+#[test]
+fn overlays_patch_graph() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::from_str("bar").unwrap());
+    g.put(1, &Hex::from_str_bytes("old"));
+    g.put(2, &Hex::from_str_bytes("kept"));
+    let mut patch: Sodg<16> = Sodg::empty(256);
+    patch.add(0);
+    patch.add(1);
+    patch.add(3);
+    patch.bind(0, 3, Label::from_str("foo").unwrap());
+    patch.bind(0, 1, Label::from_str("xyz").unwrap());
+    patch.put(1, &Hex::from_str_bytes("new"));
+    g.overlay(&patch).unwrap();
+    assert_eq!(4, g.len());
+    assert_eq!(3, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert_eq!(2, g.kid(0, Label::from_str("bar").unwrap()).unwrap());
+    assert_eq!(1, g.kid(0, Label::from_str("xyz").unwrap()).unwrap());
+    assert_eq!("new", g.data(1).unwrap().to_utf8().unwrap());
+    assert_eq!("kept", g.data(2).unwrap().to_utf8().unwrap());
+    assert!(!g.has_data(3));
+    let mut small: Sodg<16> = Sodg::empty(2);
+    assert!(small.overlay(&patch).is_err());
+    assert!(small.is_empty());
+}
+
+#[test]
+fn absorbs_graph_with_new_ids() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    let mut extra = Sodg::empty(256);
+    extra.add(0);
+    extra.add(1);
+    extra.bind(0, 1, Label::from_str("bar").unwrap());
+    extra.put(1, &Hex::from(42));
+    let ids = g.absorb(&extra).unwrap();
+    assert_eq!(4, g.len());
+    assert_eq!(2, ids[&0]);
+    assert_eq!(3, ids[&1]);
+    assert_eq!(3, g.kid(2, Label::from_str("bar").unwrap()).unwrap());
+    assert_eq!(1, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert_eq!(42, g.data(3).unwrap().to_i64().unwrap());
+}
+
+#[test]
+fn refuses_to_absorb_without_room() {
+    let mut g: Sodg<16> = Sodg::empty(2);
+    g.add(0);
+    let mut extra = Sodg::empty(2);
+    extra.add(0);
+    extra.add(1);
+    assert!(g.absorb(&extra).is_err());
+    assert_eq!(1, g.len());
+}
+
+#[test]
+fn refuses_to_absorb_into_reused_ids() {
+    let mut g: Sodg<16> = Sodg::empty(4);
+    for _ in 0..3 {
+        let v = g.next_id();
+        g.add(v);
+    }
+    assert_eq!(2, g.gc(0).unwrap().len());
+    let mut extra = Sodg::empty(2);
+    extra.add(0);
+    extra.add(1);
+    let msg = g.absorb(&extra).unwrap_err().to_string();
+    assert!(msg.contains("1 free IDs only"), "{msg}");
+    assert_eq!(1, g.len());
+}
+
+#[test]
+fn refuses_to_contract_into_full_vertex() {
+    let mut g: Sodg<2> = Sodg::empty(16);
+    for v in 0..5 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::from_str("a").unwrap());
+    g.bind(0, 2, Label::from_str("b").unwrap());
+    g.bind(1, 3, Label::from_str("x").unwrap());
+    g.bind(2, 4, Label::from_str("y").unwrap());
+    g.bind(2, 3, Label::from_str("z").unwrap());
+    assert!(g.contract(0, 2).is_err());
+    assert_eq!(5, g.len());
+    assert_eq!(2, g.kid(0, Label::from_str("b").unwrap()).unwrap());
+    assert_eq!(1, g.kid(0, Label::from_str("a").unwrap()).unwrap());
+}
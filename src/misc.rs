@@ -18,7 +18,8 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Sodg;
+use crate::{Hex, Label, Sodg, Vertex, BRANCH_NONE, MAX_BRANCH_SIZE};
+use std::mem::size_of;
 
 impl<const N: usize> Sodg<N> {
     /// Get total number of vertices in the graph.
@@ -42,6 +43,68 @@ impl<const N: usize> Sodg<N> {
             .map(|(v, _)| v)
             .collect::<Vec<usize>>()
     }
+
+    /// Estimate the amount of memory occupied by the graph, in bytes.
+    ///
+    /// The estimate includes the space allocated for all vertices and
+    /// their edges (even if they are not used yet), the
+    /// data stored in the vertices, the ordinals of edges, and the reverse
+    /// index, if it's enabled. For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// let before = g.byte_size();
+    /// g.add(0);
+    /// g.put(0, &Hex::from_str_bytes("Hello, world!"));
+    /// assert!(g.byte_size() > before);
+    /// ```
+    #[must_use]
+    pub fn byte_size(&self) -> usize {
+        let mut total = size_of::<Self>()
+            + self.vertices.capacity() * size_of::<Option<Vertex<N>>>()
+            + self.branches.capacity()
+                * size_of::<Option<microstack::Stack<usize, MAX_BRANCH_SIZE>>>()
+            + self.stores.capacity() * size_of::<Option<usize>>();
+        for (_, vtx) in self.vertices.iter() {
+            if let Hex::Vector(v) = &vtx.data {
+                total += v.capacity();
+            }
+        }
+        total += self.ords.capacity() * size_of::<((usize, Label), usize)>();
+        if let Some(idx) = &self.reverse {
+            for list in idx.values() {
+                total += size_of::<usize>() + list.capacity() * size_of::<(usize, Label)>();
+            }
+        }
+        total
+    }
+
+    /// Find which of the provided vertices are absent in the graph.
+    ///
+    /// The IDs are returned in the same order as they were provided.
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::Sodg;
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(2);
+    /// assert_eq!(vec![3, 1], g.missing(&[3, 0, 1, 2]));
+    /// ```
+    #[must_use]
+    pub fn missing(&self, ids: &[usize]) -> Vec<usize> {
+        ids.iter().copied().filter(|v| !self.alive(*v)).collect()
+    }
+
+    /// Is this vertex present in the graph and not yet collected as garbage?
+    pub(crate) fn alive(&self, v: usize) -> bool {
+        v < self.vertices.capacity()
+            && self
+                .vertices
+                .get(v)
+                .is_some_and(|vtx| vtx.branch != BRANCH_NONE)
+    }
 }
 
 #[test]
@@ -49,3 +112,22 @@ fn counts_vertices() {
     let g: Sodg<16> = Sodg::empty(256);
     assert_eq!(0, g.len());
 }
+
+#[test]
+fn finds_missing_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(1);
+    g.add(5);
+    assert_eq!(vec![7, 0, 1000], g.missing(&[7, 1, 0, 5, 1000]));
+}
+
+#[test]
+fn estimates_byte_size() {
+    let empty: Sodg<16> = Sodg::empty(256);
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..10 {
+        g.add(v);
+        g.put(v, &Hex::from_vec(vec![0xAB; 100]));
+    }
+    assert!(g.byte_size() >= empty.byte_size() + 1000);
+}
@@ -20,9 +20,10 @@
 
 use crate::{Hex, Label};
 use crate::{Persistence, Sodg, BRANCH_NONE, BRANCH_STATIC};
-use anyhow::Context;
+use anyhow::{anyhow, Context, Result};
 #[cfg(debug_assertions)]
 use log::trace;
+use std::str::FromStr;
 
 impl<const N: usize> Sodg<N> {
     /// Add a new vertex `v1` to itself.
@@ -66,6 +67,12 @@ impl<const N: usize> Sodg<N> {
     ///
     /// If an edge with this label already exists, it will be replaced with a new edge.
     ///
+    /// Bound vertices are grouped into branches, and a branch is collected
+    /// as garbage once all data in it is read by [`Sodg::data`]. There are only
+    /// a few branches and each of them holds a limited number of vertices.
+    /// If no free branch is left, or the branch is full, the vertex stays
+    /// static and is never collected.
+    ///
     /// # Panics
     ///
     /// If either vertex `v1` or `v2` is absent, an `Err` will be returned.
@@ -77,32 +84,25 @@ impl<const N: usize> Sodg<N> {
     /// If alerts trigger any error, the error will be returned here.
     #[inline]
     pub fn bind(&mut self, v1: usize, v2: usize, a: Label) {
-        let mut ours = self.vertices.get(v1).unwrap().branch;
+        let ours = self.vertices.get(v1).unwrap().branch;
         let theirs = self.vertices.get(v2).unwrap().branch;
-        let vtx1 = self.vertices.get_mut(v1).unwrap();
-        vtx1.edges.insert(a, v2);
+        self.put_edge(v1, a, v2, 0);
         if ours == BRANCH_STATIC {
             if theirs == BRANCH_STATIC {
-                for b in self.branches.iter_mut() {
-                    if b.1.is_empty() {
-                        b.1.push(v1);
-                        ours = b.0;
-                        vtx1.branch = ours;
-                        break;
-                    }
+                let fresh = self
+                    .branches
+                    .iter()
+                    .find(|(_, b)| b.is_empty())
+                    .map(|(b, _)| b);
+                if let Some(b) = fresh {
+                    self.relocate(v1, b);
+                    self.relocate(v2, b);
                 }
-                self.vertices.get_mut(v2).unwrap().branch = ours;
-                self.branches.get_mut(ours).unwrap().push(v2);
             } else {
-                vtx1.branch = theirs;
-                self.branches.get_mut(theirs).unwrap().push(v1);
-            }
-        } else {
-            let vtx2 = self.vertices.get_mut(v2).unwrap();
-            if vtx2.branch == BRANCH_STATIC {
-                vtx2.branch = ours;
-                self.branches.get_mut(ours).unwrap().push(v2);
+                self.relocate(v1, theirs);
             }
+        } else if theirs == BRANCH_STATIC {
+            self.relocate(v2, ours);
         }
         #[cfg(debug_assertions)]
         trace!(
@@ -115,6 +115,179 @@ impl<const N: usize> Sodg<N> {
         );
     }
 
+    /// Remove the edge `a` from vertex `v1` to vertex `v2`.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(42);
+    /// let foo = Label::from_str("foo").unwrap();
+    /// g.bind(0, 42, foo);
+    /// g.unbind(0, 42, foo).unwrap();
+    /// assert!(g.kid(0, foo).is_none());
+    /// ```
+    ///
+    /// The vertices stay in the graph, even if nothing points to them anymore.
+    ///
+    /// # Errors
+    ///
+    /// If there is no such edge, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn unbind(&mut self, v1: usize, v2: usize, a: Label) -> Result<()> {
+        if !self.alive(v1) {
+            return Err(anyhow!("Can't find ν{v1}"));
+        }
+        if self.kid(v1, a) != Some(v2) {
+            return Err(anyhow!("There is no edge ν{v1}.{a} → ν{v2}"));
+        }
+        self.drop_edge(v1, a);
+        #[cfg(debug_assertions)]
+        trace!("#unbind: edge removed ν{v1}.{a} → ν{v2}");
+        Ok(())
+    }
+
+    /// Change the target of the existing edge `a` departing from `v1`,
+    /// making it point to `v2`, and return the previous target.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert_eq!(1, g.rebind(0, Label::Alpha(0), 2).unwrap());
+    /// assert_eq!(2, g.kid(0, Label::Alpha(0)).unwrap());
+    /// ```
+    ///
+    /// Unlike [`Sodg::bind`], this keeps the ordinal of the edge,
+    /// set by [`Sodg::bind_ordered`].
+    ///
+    /// # Errors
+    ///
+    /// If either vertex is absent or there is no edge `a` departing
+    /// from `v1`, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn rebind(&mut self, v1: usize, a: Label, v2: usize) -> Result<usize> {
+        for v in [v1, v2] {
+            if !self.alive(v) {
+                return Err(anyhow!("Can't find ν{v}"));
+            }
+        }
+        let before = self
+            .kid(v1, a)
+            .with_context(|| format!("There is no edge ν{v1}.{a}"))?;
+        let ord = self.ord(v1, a);
+        self.bind(v1, v2, a);
+        self.put_edge(v1, a, v2, ord);
+        Ok(before)
+    }
+
+    /// Make edges `a` from each of the `parents` to the vertex `child`,
+    /// and return the number of edges made.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// assert_eq!(2, g.fan_out(&[0, 1], 2, Label::Alpha(0)).unwrap());
+    /// assert_eq!(2, g.kid(1, Label::Alpha(0)).unwrap());
+    /// ```
+    ///
+    /// Existing edges `a` of the parents are replaced.
+    ///
+    /// # Errors
+    ///
+    /// If any of the vertices is absent or a parent has no room
+    /// for one more edge, an `Err` will be returned and nothing will be bound.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn fan_out(&mut self, parents: &[usize], child: usize, a: Label) -> Result<usize> {
+        for v in parents.iter().chain(std::iter::once(&child)) {
+            if !self.alive(*v) {
+                return Err(anyhow!("Can't find ν{v}, nothing was bound"));
+            }
+        }
+        if let Some(v) = parents.iter().find(|v| {
+            self.kid(**v, a).is_none() && self.vertices.get(**v).unwrap().edges.len() >= N
+        }) {
+            return Err(anyhow!("There is no room for one more edge in ν{v}"));
+        }
+        for v in parents {
+            self.bind(*v, child, a);
+        }
+        Ok(parents.len())
+    }
+
+    /// Find the smallest label made of the `prefix` and a number,
+    /// like `α2` or `foo3`, which is not used by the edges of `v`.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// assert_eq!(Label::Alpha(1), g.next_free_label(0, "α").unwrap());
+    /// ```
+    ///
+    /// The graph is not modified.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent or the label is too long, an `Err` will be returned.
+    pub fn next_free_label(&self, v: usize, prefix: &str) -> Result<Label> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v}"));
+        }
+        for n in 0.. {
+            let a = Label::from_str(&format!("{prefix}{n}"))
+                .with_context(|| format!("Can't make a label of '{prefix}' and {n}"))?;
+            if self.kid(v, a).is_none() {
+                return Ok(a);
+            }
+        }
+        unreachable!()
+    }
+
+    /// Move the vertex `v` from the static branch to the branch `b`,
+    /// taking its stored data (if any) with it.
+    ///
+    /// If the vertex is not in the static branch (it was already moved,
+    /// for example, when it's bound to itself), or the branch `b` is full,
+    /// nothing happens: the vertex stays where it is.
+    fn relocate(&mut self, v: usize, b: usize) {
+        let vtx = self.vertices.get_mut(v).unwrap();
+        if vtx.branch != BRANCH_STATIC || self.branches.get_mut(b).unwrap().try_push(v).is_err() {
+            return;
+        }
+        vtx.branch = b;
+        if vtx.persistence == Persistence::Stored {
+            *self.stores.get_mut(BRANCH_STATIC).unwrap() -= 1;
+            *self.stores.get_mut(b).unwrap() += 1;
+        }
+    }
+
     /// Set vertex data.
     ///
     /// For example:
@@ -142,6 +315,60 @@ impl<const N: usize> Sodg<N> {
         trace!("#put: data of ν{v} set to {d}");
     }
 
+    /// Add a new vertex `v` and set its data, in one call.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::Hex;
+    /// use sodg::Sodg;
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add_with_data(42, &Hex::from(7)).unwrap();
+    /// assert_eq!(7, g.data(42).unwrap().to_i64().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` already exists, an `Err` will be returned
+    /// and nothing will be changed.
+    pub fn add_with_data(&mut self, v: usize, d: &Hex) -> Result<()> {
+        if self.alive(v) {
+            return Err(anyhow!("Vertex ν{v} already exists"));
+        }
+        self.add(v);
+        self.put(v, d);
+        Ok(())
+    }
+
+    /// Put data into many vertices at once and return the number
+    /// of vertices updated.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::Hex;
+    /// use sodg::Sodg;
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// assert_eq!(2, g.bulk_put(&[(0, Hex::from(1)), (1, Hex::from(2))]).unwrap());
+    /// assert_eq!(2, g.data(1).unwrap().to_i64().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If any of the vertices is absent, an `Err` will be returned
+    /// and no data will be changed.
+    pub fn bulk_put(&mut self, items: &[(usize, Hex)]) -> Result<usize> {
+        if let Some((v, _)) = items.iter().find(|(v, _)| !self.alive(*v)) {
+            return Err(anyhow!("Can't find ν{v}, nothing was put"));
+        }
+        for (v, d) in items {
+            self.put(*v, d);
+        }
+        Ok(items.len())
+    }
+
     /// Read vertex data, and then submit the vertex to garbage collection.
     ///
     /// For example:
@@ -170,6 +397,7 @@ impl<const N: usize> Sodg<N> {
     /// If vertex `v1` is absent, it will panic.
     #[inline]
     pub fn data(&mut self, v: usize) -> Option<Hex> {
+        self.log_query(|| format!("data(ν{v})"));
         let vtx = self.vertices.get_mut(v).unwrap();
         match vtx.persistence {
             Persistence::Stored => {
@@ -178,7 +406,7 @@ impl<const N: usize> Sodg<N> {
                 let branch = vtx.branch;
                 let s = self.stores.get_mut(branch).unwrap();
                 *s -= 1;
-                if *s == 0 {
+                if *s == 0 && branch > BRANCH_STATIC {
                     let members = self.branches.get_mut(branch).unwrap();
                     for v in members.into_iter() {
                         self.vertices.get_mut(v).unwrap().branch = BRANCH_NONE;
@@ -255,6 +483,36 @@ impl<const N: usize> Sodg<N> {
             .iter()
     }
 
+    /// Get all kids of a vertex, as pairs of labels and IDs, sorted by labels.
+    ///
+    /// This is a safe alternative to [`Sodg::kids`], which panics if the
+    /// vertex is absent. For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(42);
+    /// g.bind(0, 42, Label::from_str("b").unwrap());
+    /// g.bind(0, 42, Label::from_str("a").unwrap());
+    /// let kids = g.kids_sorted(0).unwrap();
+    /// assert_eq!("a", kids[0].0.to_string());
+    /// assert!(g.kids_sorted(42).unwrap().is_empty());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent, an `Err` will be returned.
+    pub fn kids_sorted(&self, v: usize) -> Result<Vec<(Label, usize)>> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v} in kids_sorted()"));
+        }
+        let mut kids: Vec<(Label, usize)> = self.kids(v).map(|(a, to)| (*a, *to)).collect();
+        kids.sort();
+        Ok(kids)
+    }
+
     /// Find a kid of a vertex, by its edge name, and return the ID of the vertex found.
     ///
     /// For example:
@@ -276,6 +534,7 @@ impl<const N: usize> Sodg<N> {
     #[must_use]
     #[inline]
     pub fn kid(&self, v: usize, a: Label) -> Option<usize> {
+        self.log_query(|| format!("kid(ν{v}, {a})"));
         for e in &self.vertices.get(v).unwrap().edges {
             if *e.0 == a {
                 return Some(*e.1);
@@ -283,10 +542,58 @@ impl<const N: usize> Sodg<N> {
         }
         None
     }
-}
 
-#[cfg(test)]
-use std::str::FromStr;
+    /// Find a kid of a vertex, by its edge name, ignoring the case of letters,
+    /// and return the ID of the vertex found together with a flag, which is
+    /// `true` if the name matched exactly.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(42);
+    /// g.bind(0, 42, Label::from_str("foo").unwrap());
+    /// assert_eq!(Some((42, false)), g.kid_ci(0, "Foo").unwrap());
+    /// assert_eq!(Some((42, true)), g.kid_ci(0, "foo").unwrap());
+    /// ```
+    ///
+    /// If an edge matches exactly, it wins, even if there are other
+    /// edges that match case-insensitively. Otherwise, if more than one
+    /// edge matches, the lookup is ambiguous and an `Err` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent or the match is ambiguous, an `Err` will be returned.
+    pub fn kid_ci(&self, v: usize, a: &str) -> Result<Option<(usize, bool)>> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v}"));
+        }
+        let mut found = vec![];
+        for (k, to) in self.kids(v) {
+            let name = k.to_string();
+            if name == a {
+                return Ok(Some((*to, true)));
+            }
+            if name.to_lowercase() == a.to_lowercase() {
+                found.push((name, *to));
+            }
+        }
+        if found.len() > 1 {
+            return Err(anyhow!(
+                "The name '{a}' is ambiguous in ν{v}: {}",
+                found
+                    .iter()
+                    .map(|(n, _)| n.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ));
+        }
+        Ok(found.first().map(|(_, to)| (*to, false)))
+    }
+}
 
 #[test]
 fn adds_simple_vertex() {
@@ -341,6 +648,41 @@ fn binds_two_names() {
     assert_eq!(2, g.kid(1, second).unwrap());
 }
 
+#[test]
+fn fans_out_to_many_parents() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..5 {
+        g.add(v);
+    }
+    let foo = Label::from_str("foo").unwrap();
+    assert_eq!(3, g.fan_out(&[0, 1, 2], 4, foo).unwrap());
+    for v in 0..3 {
+        assert_eq!(Some(4), g.kid(v, foo), "ν{v}");
+    }
+    assert!(g.kid(3, foo).is_none());
+    assert!(g.fan_out(&[3, 7], 4, foo).is_err());
+    assert!(g.kid(3, foo).is_none());
+}
+
+#[test]
+fn finds_next_free_label() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 1, Label::Alpha(1));
+    g.bind(0, 1, Label::from_str("x0").unwrap());
+    assert_eq!(Label::Alpha(2), g.next_free_label(0, "α").unwrap());
+    assert_eq!(
+        Label::from_str("x1").unwrap(),
+        g.next_free_label(0, "x").unwrap()
+    );
+    assert_eq!(Label::Alpha(0), g.next_free_label(1, "α").unwrap());
+    assert_eq!(3, g.kids(0).count());
+    assert!(g.next_free_label(0, "too_long").is_err());
+    assert!(g.next_free_label(5, "α").is_err());
+}
+
 #[test]
 fn overwrites_edge() {
     let mut g: Sodg<16> = Sodg::empty(256);
@@ -371,6 +713,22 @@ fn sets_simple_data() {
     assert_eq!(data, g.data(0).unwrap());
 }
 
+#[test]
+fn adds_vertex_with_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add_with_data(1, &Hex::from_str_bytes("hello")).unwrap();
+    assert_eq!(1, g.len());
+    assert_eq!("hello", g.data(1).unwrap().to_utf8().unwrap());
+}
+
+#[test]
+fn adds_existing_vertex_with_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(1);
+    assert!(g.add_with_data(1, &Hex::from(42)).is_err());
+    assert!(g.data(1).is_none());
+}
+
 #[test]
 fn collects_garbage() {
     let mut g: Sodg<16> = Sodg::empty(256);
@@ -441,3 +799,136 @@ fn adds_twice() {
     g.add(0);
     g.add(0);
 }
+
+#[test]
+fn finds_kid_ignoring_case() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    assert_eq!(Some((1, false)), g.kid_ci(0, "Foo").unwrap());
+    assert_eq!(None, g.kid_ci(0, "bar").unwrap());
+    g.add(2);
+    g.bind(0, 2, Label::from_str("FOO").unwrap());
+    assert!(g.kid_ci(0, "Foo").is_err());
+    assert_eq!(Some((2, true)), g.kid_ci(0, "FOO").unwrap());
+}
+
+#[test]
+fn puts_data_in_bulk() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    let items = vec![
+        (0, Hex::from_str_bytes("a")),
+        (1, Hex::from_str_bytes("b")),
+        (2, Hex::from_str_bytes("c")),
+    ];
+    assert_eq!(3, g.bulk_put(&items).unwrap());
+    assert_eq!("b", g.data(1).unwrap().to_utf8().unwrap());
+}
+
+#[test]
+fn aborts_bulk_put_on_absent_vertex() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let items = vec![(0, Hex::from(1)), (7, Hex::from(2))];
+    assert!(g.bulk_put(&items).is_err());
+    assert!(g.data(0).is_none());
+}
+
+#[test]
+fn binds_vertex_with_data_to_itself() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from(42));
+    g.bind(0, 0, Label::Alpha(0));
+    assert_eq!(42, g.data(0).unwrap().to_i64().unwrap());
+    assert!(g.is_empty());
+}
+
+#[test]
+fn binds_when_branches_are_exhausted() {
+    let mut g: Sodg<64> = Sodg::empty(256);
+    for v in 0..64 {
+        g.add(v);
+        g.put(v, &Hex::from(7));
+    }
+    for v in (0..64).step_by(2) {
+        g.bind(v, v + 1, Label::Alpha(0));
+    }
+    for v in 40..64 {
+        g.bind(0, v, Label::Alpha(v));
+    }
+    assert_eq!(Some(63), g.kid(62, Label::Alpha(0)));
+    assert_eq!(Some(63), g.kid(0, Label::Alpha(63)));
+    assert_eq!(7, g.data(63).unwrap().to_i64().unwrap());
+}
+
+#[test]
+fn never_collects_vertices_without_branch() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in (0..64).step_by(2) {
+        g.add(v);
+        g.add(v + 1);
+        g.bind(v, v + 1, Label::Alpha(0));
+    }
+    g.add(100);
+    g.add(101);
+    g.put(101, &Hex::from(7));
+    g.bind(100, 101, Label::Alpha(0));
+    let before = g.len();
+    assert_eq!(7, g.data(101).unwrap().to_i64().unwrap());
+    assert_eq!(before, g.len());
+    assert!(g.alive(0));
+    assert!(g.alive(100));
+    assert!(g.alive(101));
+}
+
+#[test]
+fn binds_vertex_with_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.put(1, &Hex::from(42));
+    g.bind(0, 1, Label::Alpha(0));
+    assert_eq!(42, g.data(1).unwrap().to_i64().unwrap());
+    assert!(g.is_empty());
+}
+
+#[test]
+fn unbinds_edge() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    let foo = Label::from_str("foo").unwrap();
+    g.bind(0, 1, foo);
+    assert!(g.unbind(0, 0, foo).is_err());
+    g.unbind(0, 1, foo).unwrap();
+    assert!(g.kid(0, foo).is_none());
+    assert!(g.unbind(0, 1, foo).is_err());
+    assert_eq!(2, g.len());
+}
+
+#[test]
+fn lists_sorted_kids() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.add(3);
+    g.bind(0, 3, Label::from_str("zoo").unwrap());
+    g.bind(0, 1, Label::from_str("bar").unwrap());
+    g.bind(0, 2, Label::from_str("foo").unwrap());
+    assert_eq!(
+        vec![
+            (Label::from_str("bar").unwrap(), 1),
+            (Label::from_str("foo").unwrap(), 2),
+            (Label::from_str("zoo").unwrap(), 3),
+        ],
+        g.kids_sorted(0).unwrap()
+    );
+    assert!(g.kids_sorted(1).unwrap().is_empty());
+    assert!(g.kids_sorted(4).is_err());
+}
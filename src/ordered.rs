@@ -0,0 +1,179 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Label, Sodg};
+use anyhow::{anyhow, Result};
+
+impl<const N: usize> Sodg<N> {
+    /// Make an edge `a` from vertex `v1` to vertex `v2`, like [`Sodg::bind`]
+    /// does, and give it the ordinal `ord`, used by [`Sodg::kids_ordered`].
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// g.bind_ordered(0, 1, Label::from_str("a").unwrap(), 2).unwrap();
+    /// g.bind_ordered(0, 2, Label::from_str("b").unwrap(), 1).unwrap();
+    /// assert_eq!(2, g.kids_ordered(0).unwrap()[0].1);
+    /// ```
+    ///
+    /// If the edge is later replaced by [`Sodg::bind`], its ordinal is reset to zero.
+    ///
+    /// # Errors
+    ///
+    /// If either vertex is absent, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn bind_ordered(&mut self, v1: usize, v2: usize, a: Label, ord: usize) -> Result<()> {
+        for v in [v1, v2] {
+            if !self.alive(v) {
+                return Err(anyhow!("Can't find ν{v}"));
+            }
+        }
+        self.bind(v1, v2, a);
+        self.put_edge(v1, a, v2, ord);
+        Ok(())
+    }
+
+    /// Get all kids of a vertex, as pairs of labels and IDs, sorted
+    /// by the ordinals of their edges, and then by labels.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind_ordered(0, 1, Label::Alpha(0), 1).unwrap();
+    /// g.bind(0, 1, Label::Alpha(1));
+    /// assert_eq!(
+    ///     vec![(Label::Alpha(1), 1), (Label::Alpha(0), 1)],
+    ///     g.kids_ordered(0).unwrap()
+    /// );
+    /// ```
+    ///
+    /// Edges made by [`Sodg::bind`] have the ordinal of zero.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn kids_ordered(&self, v: usize) -> Result<Vec<(Label, usize)>> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v} in kids_ordered()"));
+        }
+        let mut kids: Vec<(usize, Label, usize)> = self
+            .kids(v)
+            .map(|(a, to)| (self.ord(v, *a), *a, *to))
+            .collect();
+        kids.sort_unstable();
+        Ok(kids.into_iter().map(|(_, a, to)| (a, to)).collect())
+    }
+}
+
+#[test]
+fn retrieves_kids_in_order() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..5 {
+        g.add(v);
+    }
+    g.bind_ordered(0, 1, Label::Alpha(0), 3).unwrap();
+    g.bind_ordered(0, 2, Label::Alpha(1), 1).unwrap();
+    g.bind_ordered(0, 3, Label::Alpha(2), 2).unwrap();
+    g.bind(0, 4, Label::Alpha(3));
+    assert_eq!(
+        vec![
+            (Label::Alpha(3), 4),
+            (Label::Alpha(1), 2),
+            (Label::Alpha(2), 3),
+            (Label::Alpha(0), 1),
+        ],
+        g.kids_ordered(0).unwrap()
+    );
+    g.rebind(0, Label::Alpha(2), 4).unwrap();
+    assert_eq!((Label::Alpha(2), 4), g.kids_ordered(0).unwrap()[2]);
+    g.bind(0, 1, Label::Alpha(0));
+    assert_eq!((Label::Alpha(0), 1), g.kids_ordered(0).unwrap()[0]);
+    assert!(g.kids_ordered(4).unwrap().is_empty());
+    assert!(g.bind_ordered(0, 9, Label::Alpha(4), 0).is_err());
+    assert!(g.kids_ordered(9).is_err());
+}
+
+#[test]
+fn drops_ordinals_with_edges() {
+    let mut g: Sodg<2> = Sodg::empty(16);
+    g.add(0);
+    g.add(1);
+    g.bind_ordered(0, 1, Label::Alpha(0), 1).unwrap();
+    g.bind_ordered(0, 1, Label::Alpha(1), 1).unwrap();
+    assert_eq!(2, g.retain_edges(|_, _, _| false));
+    g.bind_ordered(0, 1, Label::Alpha(2), 1).unwrap();
+    g.bind(0, 1, Label::Alpha(3));
+    assert_eq!(Label::Alpha(2), g.kids_ordered(0).unwrap()[1].0);
+}
+
+#[test]
+fn moves_ordinals_with_renamed_edges() {
+    let mut g: Sodg<16> = Sodg::empty(16);
+    g.add(0);
+    g.add(1);
+    g.bind_ordered(0, 1, Label::Alpha(0), 2).unwrap();
+    g.bind_ordered(0, 1, Label::Alpha(1), 1).unwrap();
+    let map = std::collections::HashMap::from([
+        (Label::Alpha(0), Label::Alpha(1)),
+        (Label::Alpha(1), Label::Alpha(0)),
+    ]);
+    assert_eq!(2, g.rename_attrs(&map).unwrap());
+    assert_eq!(
+        vec![(Label::Alpha(0), 1), (Label::Alpha(1), 1)],
+        g.kids_ordered(0).unwrap()
+    );
+    g.bind(0, 1, Label::Alpha(1));
+    assert_eq!(Label::Alpha(0), g.kids_ordered(0).unwrap()[1].0);
+}
+
+#[test]
+fn keeps_ordinals_on_contraction() {
+    let mut g: Sodg<16> = Sodg::empty(16);
+    for v in 0..4 {
+        g.add(v);
+    }
+    g.bind_ordered(0, 2, Label::Alpha(0), 3).unwrap();
+    g.bind_ordered(2, 3, Label::Alpha(1), 2).unwrap();
+    g.bind(1, 3, Label::Alpha(2));
+    g.contract(1, 2).unwrap();
+    assert_eq!(vec![(Label::Alpha(0), 1)], g.kids_ordered(0).unwrap());
+    assert_eq!(
+        vec![(Label::Alpha(2), 3), (Label::Alpha(1), 3)],
+        g.kids_ordered(1).unwrap()
+    );
+    assert_eq!(0, g.ord(2, Label::Alpha(1)));
+}
@@ -0,0 +1,228 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Label, Sodg};
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+impl<const N: usize> Sodg<N> {
+    /// Turn on or off the reverse index of edges, which makes
+    /// [`Sodg::parents`] fast, while making [`Sodg::bind`] a bit slower.
+    ///
+    /// The index is on by default. It may be turned off, in order to save
+    /// memory and time while building a large graph, and then turned on again:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.enable_reverse_index(false);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.enable_reverse_index(true);
+    /// assert_eq!(vec![(0, Label::Alpha(0))], g.parents(1).unwrap());
+    /// ```
+    ///
+    /// When turned on, the index is built from all existing edges
+    /// and then kept in sync with all further modifications of the graph.
+    /// The index is not saved by [`Sodg::save`], but is rebuilt by [`Sodg::load`].
+    pub fn enable_reverse_index(&mut self, on: bool) {
+        if on {
+            self.reverse = Some(HashMap::new());
+            self.reindex();
+        } else {
+            self.reverse = None;
+        }
+    }
+
+    /// Find all vertices that have edges arriving to the vertex `v`,
+    /// together with the labels of these edges, sorted.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.bind(1, 1, Label::Alpha(0));
+    /// assert_eq!(vec![(0, Label::Alpha(0)), (1, Label::Alpha(0))], g.parents(1).unwrap());
+    /// ```
+    ///
+    /// If the reverse index is turned off by [`Sodg::enable_reverse_index`],
+    /// the entire graph is scanned.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `v` is absent, an `Err` will be returned.
+    pub fn parents(&self, v: usize) -> Result<Vec<(usize, Label)>> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v} in parents()"));
+        }
+        let mut found: Vec<(usize, Label)> = self.reverse.as_ref().map_or_else(
+            || {
+                self.keys()
+                    .into_iter()
+                    .flat_map(|u| {
+                        self.kids(u)
+                            .filter(|(_, to)| **to == v)
+                            .map(move |(a, _)| (u, *a))
+                    })
+                    .collect()
+            },
+            |idx| {
+                idx.get(&v)
+                    .map(|list| {
+                        list.iter()
+                            .filter(|(u, _)| self.alive(*u))
+                            .copied()
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            },
+        );
+        found.sort_unstable();
+        Ok(found)
+    }
+
+    /// Register the edge `v1.a ➞ v2` in the reverse index, if it's enabled.
+    pub(crate) fn index_edge(&mut self, v1: usize, a: Label, v2: usize) {
+        if let Some(idx) = &mut self.reverse {
+            idx.entry(v2).or_default().push((v1, a));
+        }
+    }
+
+    /// Remove the edge `v1.a ➞ v2` from the reverse index, if it's enabled.
+    pub(crate) fn unindex_edge(&mut self, v1: usize, a: Label, v2: usize) {
+        if let Some(list) = self.reverse.as_mut().and_then(|idx| idx.get_mut(&v2)) {
+            if let Some(pos) = list.iter().position(|e| *e == (v1, a)) {
+                list.swap_remove(pos);
+            }
+        }
+    }
+
+    /// Rebuild the reverse index from scratch, if it's enabled.
+    pub(crate) fn reindex(&mut self) {
+        if self.reverse.is_none() {
+            return;
+        }
+        let mut idx: HashMap<usize, Vec<(usize, Label)>> = HashMap::new();
+        for (v, vtx) in self.vertices.iter() {
+            for (a, to) in &vtx.edges {
+                idx.entry(*to).or_default().push((v, *a));
+            }
+        }
+        self.reverse = Some(idx);
+    }
+}
+
+#[cfg(test)]
+use crate::Hex;
+
+#[test]
+fn keeps_reverse_index_in_sync() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.enable_reverse_index(false);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::Alpha(0));
+    g.enable_reverse_index(true);
+    for v in 2..8 {
+        g.add(v);
+        g.bind(v - 1, v, Label::Alpha(0));
+        g.bind(v, 0, Label::Alpha(1));
+    }
+    g.bind(3, 7, Label::Alpha(0));
+    g.bind(0, 7, Label::Alpha(0));
+    g.retain_edges(|v, a, _| v != 5 || a != Label::Alpha(1));
+    g.contract(2, 6).unwrap();
+    g.put(4, &Hex::from(42));
+    g.add(9);
+    g.bind(9, 4, Label::Alpha(2));
+    g.add(10);
+    g.trim(&[]);
+    let mut plain = g.clone();
+    plain.enable_reverse_index(false);
+    for v in 0..12 {
+        assert_eq!(plain.parents(v).ok(), g.parents(v).ok(), "ν{v}");
+    }
+    assert_eq!(
+        vec![(0, Label::Alpha(0)), (3, Label::Alpha(0))],
+        g.parents(7).unwrap()
+    );
+}
+
+#[test]
+fn finds_parents_after_rebinding() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..4 {
+        g.add(v);
+    }
+    g.bind(0, 3, Label::Alpha(0));
+    g.bind(1, 3, Label::Greek('φ'));
+    g.bind(2, 3, Label::Alpha(1));
+    g.bind(2, 1, Label::Alpha(1));
+    g.unbind(1, 3, Label::Greek('φ')).unwrap();
+    assert_eq!(vec![(0, Label::Alpha(0))], g.parents(3).unwrap());
+    assert_eq!(vec![(2, Label::Alpha(1))], g.parents(1).unwrap());
+    assert!(g.parents(2).unwrap().is_empty());
+    assert!(g.parents(9).is_err());
+}
+
+#[test]
+fn finds_parents_in_large_graph() {
+    let total = 4000;
+    let bound = 224;
+    let mut g: Sodg<16> = Sodg::empty(total);
+    for v in 0..total {
+        g.add(v);
+    }
+    for v in (0..bound).filter(|v| v % 16 != 0) {
+        g.bind(v - 1, v, Label::Alpha(0));
+    }
+    for v in 0..bound {
+        g.bind(v, (v * 7 + 16) % bound, Label::Alpha(1));
+        g.bind(v, 0, Label::Alpha(2));
+    }
+    let mut plain = g.clone();
+    plain.enable_reverse_index(false);
+    for v in (0..total).step_by(7) {
+        assert_eq!(plain.parents(v).ok(), g.parents(v).ok(), "ν{v}");
+    }
+    assert_eq!(vec![(41, Label::Alpha(0))], g.parents(42).unwrap());
+    assert_eq!(bound, g.parents(0).unwrap().len());
+    assert!(g.parents(total - 1).unwrap().is_empty());
+}
+
+#[test]
+fn keeps_reverse_index_by_default() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let file = tmp.path().join("foo.sodg");
+    let mut g: Sodg<16> = Sodg::empty(256);
+    assert!(g.reverse.is_some());
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::Alpha(0));
+    g.save(&file).unwrap();
+    let after = Sodg::<16>::load(&file).unwrap();
+    assert!(after.reverse.is_some());
+    assert_eq!(vec![(0, Label::Alpha(0))], after.parents(1).unwrap());
+}
@@ -0,0 +1,516 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Hex, Label, Sodg};
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+
+/// How many steps [`Sodg::count_paths`] may make before giving up.
+const MAX_PATH_STEPS: usize = 1_000_000;
+
+impl<const N: usize> Sodg<N> {
+    /// Build a path of fresh vertices, starting from the vertex `start`,
+    /// binding them one after another with the provided labels, and
+    /// return the ID of the last vertex created.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// let foo = Label::from_str("foo").unwrap();
+    /// let bar = Label::from_str("bar").unwrap();
+    /// let v = g.bind_path(0, &[foo, bar]).unwrap();
+    /// assert_eq!(v, g.kid(g.kid(0, foo).unwrap(), bar).unwrap());
+    /// ```
+    ///
+    /// If `labels` is empty, `start` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `start` is absent, an `Err` will be returned.
+    pub fn bind_path(&mut self, start: usize, labels: &[Label]) -> Result<usize> {
+        if !self.alive(start) {
+            return Err(anyhow!("Can't find ν{start}"));
+        }
+        let mut v = start;
+        for a in labels {
+            let id = self.next_id();
+            self.add(id);
+            self.bind(v, id, *a);
+            v = id;
+        }
+        Ok(v)
+    }
+
+    /// Make sure there is a path from the vertex `start` with the provided
+    /// labels, reusing the edges that already exist and creating fresh
+    /// vertices only for the missing ones, and return the ID of the last vertex.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// let foo = Label::from_str("foo").unwrap();
+    /// let bar = Label::from_str("bar").unwrap();
+    /// let v = g.ensure_path(0, &[foo, bar]).unwrap();
+    /// assert_eq!(v, g.ensure_path(0, &[foo, bar]).unwrap());
+    /// assert_eq!(3, g.len());
+    /// ```
+    ///
+    /// If `labels` is empty, `start` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `start` is absent, an `Err` will be returned.
+    pub fn ensure_path(&mut self, start: usize, labels: &[Label]) -> Result<usize> {
+        if !self.alive(start) {
+            return Err(anyhow!("Can't find ν{start}"));
+        }
+        let mut v = start;
+        for (i, a) in labels.iter().enumerate() {
+            match self.kid(v, *a).filter(|k| self.alive(*k)) {
+                Some(k) => v = k,
+                None => return self.bind_path(v, &labels[i..]),
+            }
+        }
+        Ok(v)
+    }
+
+    /// Find a vertex by walking the edges from the vertex `start`,
+    /// following the labels in the dot-separated locator, like `foo.bar`.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// g.add(2);
+    /// g.bind(1, 2, Label::from_str("bar").unwrap());
+    /// assert_eq!(2, g.find(0, "foo.bar").unwrap());
+    /// ```
+    ///
+    /// If the locator is empty, `start` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `start` is absent, a label can't be parsed, or there
+    /// is no edge with the label, an `Err` will be returned, naming the
+    /// segment of the locator that failed.
+    pub fn find(&self, start: usize, locator: &str) -> Result<usize> {
+        self.log_query(|| format!("find(ν{start}, '{locator}')"));
+        if !self.alive(start) {
+            return Err(anyhow!("Can't find ν{start}"));
+        }
+        let mut v = start;
+        if locator.is_empty() {
+            return Ok(v);
+        }
+        for (pos, seg) in locator.split('.').enumerate() {
+            let a = Label::from_str(seg).with_context(|| {
+                format!("Can't parse the segment no.{pos} '{seg}' in '{locator}'")
+            })?;
+            v = self.kid(v, a).filter(|k| self.alive(*k)).with_context(|| {
+                format!("Can't find '{seg}' in ν{v}, the segment no.{pos} in '{locator}'")
+            })?;
+        }
+        Ok(v)
+    }
+
+    /// Count all simple directed paths from vertex `from` to vertex `to`,
+    /// where no vertex is visited twice.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.add(2);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.bind(0, 2, Label::Alpha(1));
+    /// g.bind(1, 2, Label::Alpha(0));
+    /// assert_eq!(2, g.count_paths(0, 2).unwrap());
+    /// ```
+    ///
+    /// If `from` equals to `to`, there is exactly one path: the empty one.
+    ///
+    /// The number of simple paths may grow exponentially with the
+    /// size of the graph, especially when it has many cycles. That's why
+    /// the search stops with an `Err` after a million steps.
+    ///
+    /// # Errors
+    ///
+    /// If either `from` or `to` is absent, or the search
+    /// is too long, an `Err` will be returned.
+    pub fn count_paths(&self, from: usize, to: usize) -> Result<usize> {
+        for v in [from, to] {
+            if !self.alive(v) {
+                return Err(anyhow!("Can't find ν{v}"));
+            }
+        }
+        if from == to {
+            return Ok(1);
+        }
+        let kids = |v: usize| -> Vec<usize> {
+            self.kids(v)
+                .map(|(_, k)| *k)
+                .filter(|k| self.alive(*k))
+                .collect()
+        };
+        let mut seen = HashSet::from([from]);
+        let mut stack = vec![(from, kids(from))];
+        let mut steps = 1;
+        let mut total = 0;
+        while let Some((_, pending)) = stack.last_mut() {
+            let Some(k) = pending.pop() else {
+                if let Some((v, _)) = stack.pop() {
+                    seen.remove(&v);
+                }
+                continue;
+            };
+            if seen.contains(&k) {
+                continue;
+            }
+            steps += 1;
+            if steps > MAX_PATH_STEPS {
+                return Err(anyhow!(
+                    "Too many paths to count, gave up after {MAX_PATH_STEPS} steps"
+                ));
+            }
+            if k == to {
+                total += 1;
+                continue;
+            }
+            seen.insert(k);
+            stack.push((k, kids(k)));
+        }
+        Ok(total)
+    }
+
+    /// Find one of the shortest label paths from the vertex `root`
+    /// to every vertex reachable from it.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// let paths = g.paths_from_root(0).unwrap();
+    /// assert_eq!("foo", paths[&1][0].to_string());
+    /// assert!(paths[&0].is_empty());
+    /// ```
+    ///
+    /// Among the paths of the same length, the one with the smallest
+    /// labels is chosen. Unreachable vertices are not in the map.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `root` is absent, an `Err` will be returned.
+    pub fn paths_from_root(&self, root: usize) -> Result<HashMap<usize, Vec<Label>>> {
+        if !self.alive(root) {
+            return Err(anyhow!("Can't find ν{root}"));
+        }
+        let mut paths = HashMap::new();
+        paths.insert(root, vec![]);
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        while let Some(v) = queue.pop_front() {
+            for (a, to) in self.kids(v).sorted() {
+                if paths.contains_key(to) || !self.alive(*to) {
+                    continue;
+                }
+                let mut path: Vec<Label> = paths[&v].clone();
+                path.push(*a);
+                paths.insert(*to, path);
+                queue.push_back(*to);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Find the longest common prefix of the label paths from the vertex
+    /// `root` to the vertices `a` and `b`.
+    ///
+    /// The paths are the same as the ones found by [`Sodg::paths_from_root`].
+    /// For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// g.add(2);
+    /// g.bind(1, 2, Label::from_str("x").unwrap());
+    /// g.add(3);
+    /// g.bind(1, 3, Label::from_str("y").unwrap());
+    /// let prefix = g.common_prefix(0, 2, 3).unwrap();
+    /// assert_eq!(vec![Label::from_str("foo").unwrap()], prefix);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `root` is absent or either `a` or `b` is not reachable
+    /// from it, an `Err` will be returned.
+    pub fn common_prefix(&self, root: usize, a: usize, b: usize) -> Result<Vec<Label>> {
+        let paths = self.paths_from_root(root)?;
+        let left = paths
+            .get(&a)
+            .with_context(|| format!("Can't reach ν{a} from ν{root}"))?;
+        let right = paths
+            .get(&b)
+            .with_context(|| format!("Can't reach ν{b} from ν{root}"))?;
+        Ok(left
+            .iter()
+            .zip(right.iter())
+            .take_while(|(x, y)| x == y)
+            .map(|(x, _)| *x)
+            .collect())
+    }
+
+    /// Find all leaves with data reachable from the vertex `root`
+    /// and map their dotted label paths to their data.
+    ///
+    /// A leaf is a vertex without kids. The paths are the same as the ones
+    /// found by [`Sodg::paths_from_root`]. The data is not taken from the
+    /// vertices, so they are not submitted to garbage collection. For example:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Hex, Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// g.add(2);
+    /// g.bind(1, 2, Label::from_str("bar").unwrap());
+    /// g.put(2, &Hex::from(42));
+    /// let values = g.leaf_values(0).unwrap();
+    /// assert_eq!(42, values["foo.bar"].to_i64().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `root` is absent, an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn leaf_values(&self, root: usize) -> Result<HashMap<String, Hex>> {
+        Ok(self
+            .paths_from_root(root)?
+            .into_iter()
+            .filter(|(v, _)| self.has_data(*v) && self.kids(*v).all(|(_, k)| !self.alive(*k)))
+            .map(|(v, path)| {
+                (
+                    path.iter().map(ToString::to_string).join("."),
+                    self.vertices.get(v).unwrap().data.clone(),
+                )
+            })
+            .collect())
+    }
+}
+
+#[test]
+fn binds_a_path() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let labels = [
+        Label::from_str("a").unwrap(),
+        Label::from_str("b").unwrap(),
+        Label::from_str("c").unwrap(),
+    ];
+    let v = g.bind_path(0, &labels).unwrap();
+    assert_eq!(4, g.len());
+    let mut found = 0;
+    for a in labels {
+        found = g.kid(found, a).unwrap();
+    }
+    assert_eq!(v, found);
+}
+
+#[test]
+fn binds_a_path_from_absent_vertex() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    assert!(g.bind_path(0, &[Label::Alpha(0)]).is_err());
+}
+
+#[test]
+fn counts_paths_in_dag() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..5 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 2, Label::Alpha(1));
+    g.bind(1, 3, Label::Alpha(0));
+    g.bind(2, 3, Label::Alpha(0));
+    g.bind(1, 2, Label::Alpha(1));
+    g.bind(3, 4, Label::Alpha(0));
+    g.bind(0, 4, Label::Alpha(2));
+    assert_eq!(4, g.count_paths(0, 4).unwrap());
+    assert_eq!(1, g.count_paths(2, 2).unwrap());
+    assert_eq!(0, g.count_paths(4, 0).unwrap());
+}
+
+#[test]
+fn counts_paths_in_cycle() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(1, 0, Label::Alpha(0));
+    assert_eq!(1, g.count_paths(0, 1).unwrap());
+    assert!(g.count_paths(0, 7).is_err());
+}
+
+#[test]
+fn counts_paths_in_long_chain() {
+    let total = 100_000;
+    let mut g: Sodg<16> = Sodg::empty(total);
+    g.add(0);
+    for v in 1..total {
+        g.add(v);
+        g.bind(v - 1, v, Label::Alpha(0));
+    }
+    assert_eq!(1, g.count_paths(0, total - 1).unwrap());
+}
+
+#[test]
+fn finds_paths_from_root() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..5 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::from_str("a").unwrap());
+    g.bind(0, 2, Label::from_str("b").unwrap());
+    g.bind(1, 3, Label::from_str("c").unwrap());
+    g.bind(2, 3, Label::from_str("d").unwrap());
+    g.bind(3, 0, Label::from_str("up").unwrap());
+    let paths = g.paths_from_root(0).unwrap();
+    assert_eq!(4, paths.len());
+    assert_eq!(
+        "a.c",
+        paths[&3]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join(".")
+    );
+    assert!(!paths.contains_key(&4));
+}
+
+#[test]
+fn finds_common_prefix_of_two_leaves() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::from_str("a").unwrap());
+    g.add(2);
+    g.bind(1, 2, Label::from_str("b").unwrap());
+    g.add(3);
+    g.bind(2, 3, Label::from_str("c").unwrap());
+    g.add(4);
+    g.bind(2, 4, Label::from_str("d").unwrap());
+    g.add(5);
+    g.bind(0, 5, Label::from_str("e").unwrap());
+    assert_eq!(
+        vec![Label::from_str("a").unwrap(), Label::from_str("b").unwrap()],
+        g.common_prefix(0, 3, 4).unwrap()
+    );
+    assert!(g.common_prefix(0, 3, 5).unwrap().is_empty());
+    assert!(g.common_prefix(1, 3, 5).is_err());
+}
+
+#[test]
+fn extends_existing_path() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let a = Label::from_str("a").unwrap();
+    let b = Label::from_str("b").unwrap();
+    let c = Label::from_str("c").unwrap();
+    let first = g.bind_path(0, &[a]).unwrap();
+    let last = g.ensure_path(0, &[a, b, c]).unwrap();
+    assert_eq!(4, g.len());
+    assert_eq!(Some(first), g.kid(0, a));
+    assert_eq!(last, g.kid(g.kid(first, b).unwrap(), c).unwrap());
+    assert_eq!(last, g.ensure_path(0, &[a, b, c]).unwrap());
+    assert_eq!(4, g.len());
+}
+
+#[test]
+fn harvests_leaf_values() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let a = Label::from_str("a").unwrap();
+    let b = Label::from_str("b").unwrap();
+    let c = Label::from_str("c").unwrap();
+    let ab = g.bind_path(0, &[a, b]).unwrap();
+    g.put(ab, &Hex::from(1));
+    let ac = g.ensure_path(0, &[a, c]).unwrap();
+    g.put(ac, &Hex::from(2));
+    let empty = g.bind_path(0, &[b]).unwrap();
+    g.put(g.kid(0, a).unwrap(), &Hex::from(3));
+    let values = g.leaf_values(0).unwrap();
+    assert_eq!(2, values.len());
+    assert_eq!(1, values["a.b"].to_i64().unwrap());
+    assert_eq!(2, values["a.c"].to_i64().unwrap());
+    assert!(!values.contains_key("b"));
+    assert!(g.has_data(ab) && !g.has_data(empty));
+}
+
+#[test]
+fn finds_vertex_by_locator() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let labels = ["a", "b", "c"].map(|a| Label::from_str(a).unwrap());
+    let last = g.bind_path(0, &labels).unwrap();
+    assert_eq!(last, g.find(0, "a.b.c").unwrap());
+    assert_eq!(0, g.find(0, "").unwrap());
+}
+
+#[test]
+fn reports_failed_segment_of_locator() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let labels = ["a", "b", "c"].map(|a| Label::from_str(a).unwrap());
+    g.bind_path(0, &labels).unwrap();
+    let a = g.kid(0, labels[0]).unwrap();
+    let err = g.find(0, "a.x.c").unwrap_err().to_string();
+    assert!(err.contains(&format!("'x' in ν{a}")), "{err}");
+}
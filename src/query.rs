@@ -0,0 +1,102 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{QueryLogger, Sodg};
+use std::sync::Mutex;
+
+impl<const N: usize> Sodg<N> {
+    /// Register a callback, which will get a short description of
+    /// every read operation, like [`Sodg::kid`], [`Sodg::find`],
+    /// or [`Sodg::data`].
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// let log = Arc::new(Mutex::new(vec![]));
+    /// let sink = log.clone();
+    /// g.set_query_logger(Box::new(move |q| sink.lock().unwrap().push(q.to_string())));
+    /// g.kid(0, Label::Alpha(0));
+    /// assert_eq!(1, log.lock().unwrap().len());
+    /// ```
+    ///
+    /// When there is no callback, which is the default, reading
+    /// costs nothing extra. The callback is not cloned together with the graph.
+    pub fn set_query_logger(&mut self, cb: QueryLogger) {
+        self.logger = Some(Mutex::new(cb));
+    }
+
+    /// Remove the callback registered by [`Sodg::set_query_logger`].
+    pub fn unset_query_logger(&mut self) {
+        self.logger = None;
+    }
+
+    /// Tell the callback, if it's registered, about a read operation.
+    ///
+    /// The description is only made if there is a callback.
+    #[inline]
+    pub(crate) fn log_query(&self, q: impl FnOnce() -> String) {
+        if let Some(m) = &self.logger {
+            if let Ok(mut cb) = m.lock() {
+                cb(&q());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::{Hex, Label};
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[cfg(test)]
+use std::sync::Arc;
+
+#[test]
+fn logs_reads_during_find() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.add(2);
+    g.bind(1, 2, Label::from_str("bar").unwrap());
+    g.put(2, &Hex::from(42));
+    let log = Arc::new(Mutex::new(vec![]));
+    let sink = log.clone();
+    g.set_query_logger(Box::new(move |q| sink.lock().unwrap().push(q.to_string())));
+    let v = g.find(0, "foo.bar").unwrap();
+    g.data(v);
+    assert_eq!(
+        vec![
+            "find(ν0, 'foo.bar')",
+            "kid(ν0, foo)",
+            "kid(ν1, bar)",
+            "data(ν2)"
+        ],
+        *log.lock().unwrap()
+    );
+    g.unset_query_logger();
+    assert!(g.kid(0, Label::from_str("foo").unwrap()).is_some());
+    assert_eq!(4, log.lock().unwrap().len());
+}
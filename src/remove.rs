@@ -0,0 +1,323 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Hex, Label, Persistence, Sodg, Vertex, BRANCH_STATIC};
+use anyhow::{anyhow, Context, Result};
+#[cfg(debug_assertions)]
+use log::trace;
+use std::collections::HashSet;
+
+impl<const N: usize> Sodg<N> {
+    /// Remove all vertices that have no edges, neither departing
+    /// nor arriving, and no data, except the ones mentioned in `keep`.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.add(2);
+    /// g.add(3);
+    /// assert_eq!(1, g.trim(&[3]));
+    /// assert_eq!(3, g.len());
+    /// ```
+    ///
+    /// The function returns the number of vertices removed.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn trim(&mut self, keep: &[usize]) -> usize {
+        let mut busy = HashSet::new();
+        for v in self.keys() {
+            let vtx = self.vertices.get(v).unwrap();
+            if !vtx.edges.is_empty() || vtx.persistence != Persistence::Empty {
+                busy.insert(v);
+            }
+            for (_, to) in &vtx.edges {
+                busy.insert(*to);
+            }
+        }
+        let mut total = 0;
+        for v in self.keys() {
+            if !busy.contains(&v) && !keep.contains(&v) {
+                self.remove(v);
+                total += 1;
+            }
+        }
+        total
+    }
+
+    /// Remove all vertices that are farther than `max_depth` edges
+    /// from the vertex `root`, together with all edges arriving to them.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.add(2);
+    /// g.bind(1, 2, Label::Alpha(0));
+    /// assert_eq!(1, g.prune_depth(0, 1).unwrap());
+    /// assert_eq!(2, g.len());
+    /// ```
+    ///
+    /// The distance is the length of the shortest path from `root`.
+    /// Vertices not reachable from `root` are not touched.
+    /// The function returns the number of vertices removed.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `root` is absent, an `Err` will be returned.
+    pub fn prune_depth(&mut self, root: usize, max_depth: usize) -> Result<usize> {
+        let far: HashSet<usize> = self
+            .paths_from_root(root)?
+            .into_iter()
+            .filter(|(_, path)| path.len() > max_depth)
+            .map(|(v, _)| v)
+            .collect();
+        for v in &far {
+            self.remove(*v);
+        }
+        self.retain_edges(|_, _, to| !far.contains(&to));
+        Ok(far.len())
+    }
+
+    /// Remove all vertices that are not reachable from the vertex `root`.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.add(2);
+    /// assert_eq!(vec![2], g.gc(0).unwrap());
+    /// assert_eq!(2, g.len());
+    /// ```
+    ///
+    /// The `root` always survives, even if it has no edges.
+    /// The function returns the IDs of the removed vertices, in
+    /// ascending order.
+    ///
+    /// # Errors
+    ///
+    /// If vertex `root` is absent, an `Err` will be returned.
+    pub fn gc(&mut self, root: usize) -> Result<Vec<usize>> {
+        if !self.alive(root) {
+            return Err(anyhow!("Can't find ν{root}"));
+        }
+        let alive = self.reachable(&[root]);
+        let dead: Vec<usize> = self
+            .keys()
+            .into_iter()
+            .filter(|v| !alive.contains(v))
+            .collect();
+        for v in &dead {
+            self.remove(*v);
+        }
+        #[cfg(debug_assertions)]
+        trace!(
+            "#gc: {} vertices unreachable from ν{root} removed",
+            dead.len()
+        );
+        Ok(dead)
+    }
+
+    /// Replace the subtree under the edge `a` of the vertex `parent` with
+    /// another subtree, rooted at `new_root`, and remove all vertices of
+    /// the old subtree that become unreachable.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.add(2);
+    /// g.replace_subtree(0, Label::Alpha(0), 2).unwrap();
+    /// assert_eq!(2, g.kid(0, Label::Alpha(0)).unwrap());
+    /// assert_eq!(2, g.len());
+    /// ```
+    ///
+    /// A vertex of the old subtree survives if it is still reachable
+    /// from `parent` or from any vertex outside of the old subtree.
+    ///
+    /// # Errors
+    ///
+    /// If `parent` or `new_root` is absent, or there is no edge `a`
+    /// departing from `parent`, an `Err` will be returned.
+    pub fn replace_subtree(&mut self, parent: usize, a: Label, new_root: usize) -> Result<()> {
+        for v in [parent, new_root] {
+            if !self.alive(v) {
+                return Err(anyhow!("Can't find ν{v}"));
+            }
+        }
+        let old = self
+            .kid(parent, a)
+            .with_context(|| format!("There is no edge ν{parent}.{a}"))?;
+        self.bind(parent, new_root, a);
+        let subtree = self.reachable(&[old]);
+        let mut roots: Vec<usize> = self
+            .keys()
+            .into_iter()
+            .filter(|v| !subtree.contains(v))
+            .collect();
+        roots.push(parent);
+        let alive = self.reachable(&roots);
+        for v in subtree {
+            if !alive.contains(&v) {
+                self.remove(v);
+            }
+        }
+        Ok(())
+    }
+
+    /// Find all live vertices reachable from the provided ones, including
+    /// themselves.
+    pub(crate) fn reachable(&self, from: &[usize]) -> HashSet<usize> {
+        let mut seen: HashSet<usize> = from.iter().copied().filter(|v| self.alive(*v)).collect();
+        let mut todo: Vec<usize> = seen.iter().copied().collect();
+        while let Some(v) = todo.pop() {
+            for (_, to) in self.kids(v) {
+                if self.alive(*to) && seen.insert(*to) {
+                    todo.push(*to);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Remove the vertex `v` entirely, together with its departing
+    /// edges and data.
+    ///
+    /// The edges arriving to `v` are not touched.
+    pub(crate) fn remove(&mut self, v: usize) {
+        let departing: Vec<Label> = self.kids(v).map(|(a, _)| *a).collect();
+        for a in departing {
+            self.drop_edge(v, a);
+        }
+        let vtx = self.vertices.get(v).unwrap();
+        let branch = vtx.branch;
+        if vtx.persistence == Persistence::Stored {
+            let s = self.stores.get_mut(branch).unwrap();
+            *s = s.saturating_sub(1);
+        }
+        if branch > BRANCH_STATIC {
+            let members = self.branches.get_mut(branch).unwrap();
+            *members =
+                microstack::Stack::from_vec(members.into_iter().filter(|m| *m != v).collect());
+        }
+        self.vertices.insert(
+            v,
+            Vertex {
+                branch: 0,
+                data: Hex::empty(),
+                persistence: Persistence::Empty,
+                edges: micromap::Map::new(),
+            },
+        );
+        #[cfg(debug_assertions)]
+        trace!("#remove: vertex ν{v} removed");
+    }
+}
+
+#[test]
+fn trims_debris() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::Alpha(0));
+    g.add(2);
+    g.put(2, &Hex::from(42));
+    g.add(3);
+    g.add(4);
+    g.add(5);
+    assert_eq!(2, g.trim(&[4]));
+    assert_eq!(vec![0, 1, 2, 4], g.keys());
+}
+
+#[test]
+fn replaces_subtree() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..7 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(1, 2, Label::Alpha(0));
+    g.bind(1, 3, Label::Alpha(1));
+    g.bind(3, 0, Label::Alpha(0));
+    g.bind(4, 3, Label::Alpha(0));
+    g.bind(5, 6, Label::Alpha(0));
+    g.replace_subtree(0, Label::Alpha(0), 5).unwrap();
+    assert_eq!(5, g.kid(0, Label::Alpha(0)).unwrap());
+    assert_eq!(vec![0, 3, 4, 5, 6], g.keys());
+    assert!(g.replace_subtree(0, Label::Alpha(7), 5).is_err());
+}
+
+#[test]
+fn prunes_deep_chain() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let last = g.bind_path(0, &[Label::Alpha(0); 5]).unwrap();
+    g.add(42);
+    g.bind(42, last, Label::Alpha(0));
+    assert_eq!(3, g.prune_depth(0, 2).unwrap());
+    assert_eq!(4, g.len());
+    let second = g
+        .kid(g.kid(0, Label::Alpha(0)).unwrap(), Label::Alpha(0))
+        .unwrap();
+    assert!(g.kid(second, Label::Alpha(0)).is_none());
+    assert!(g.kid(42, Label::Alpha(0)).is_none());
+}
+
+#[test]
+fn collects_unreachable_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..4 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(1, 2, Label::Alpha(0));
+    g.bind(2, 0, Label::Alpha(0));
+    g.bind(3, 1, Label::Alpha(0));
+    assert_eq!(vec![3], g.gc(0).unwrap());
+    assert_eq!(vec![0, 1, 2], g.keys());
+    assert!(g.gc(3).is_err());
+}
+
+#[test]
+fn keeps_lonely_root() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(7);
+    g.add(8);
+    assert_eq!(vec![8], g.gc(7).unwrap());
+    assert_eq!(vec![7], g.keys());
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Label, Persistence, Sodg};
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+
+impl<const N: usize> Sodg<N> {
+    /// Make a new graph with the same vertices and data, but
+    /// with all edges flipped: every `v1.a ➞ v2` becomes `v2.a ➞ v1`.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// let r = g.reverse().unwrap();
+    /// assert_eq!(0, r.kid(1, Label::Alpha(0)).unwrap());
+    /// assert!(r.kid(0, Label::Alpha(0)).is_none());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a vertex has more incoming edges than `N`, or two incoming
+    /// edges with the same label, which can't both be flipped,
+    /// an `Err` will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn reverse(&self) -> Result<Self> {
+        let mut arriving: BTreeMap<usize, BTreeMap<Label, usize>> = BTreeMap::new();
+        for v in self.keys() {
+            for (a, to) in self.kids(v).filter(|(_, to)| self.alive(**to)) {
+                let labels = arriving.entry(*to).or_default();
+                if let Some(before) = labels.insert(*a, v) {
+                    return Err(anyhow!(
+                        "Both ν{before} and ν{v} have edges '{a}' to ν{to}, can't reverse"
+                    ));
+                }
+                if labels.len() > N {
+                    return Err(anyhow!(
+                        "There are more than {N} edges arriving to ν{to}, can't reverse"
+                    ));
+                }
+            }
+        }
+        let mut g = Self::empty(self.vertices.capacity());
+        for v in self.keys() {
+            g.add(v);
+        }
+        for (to, labels) in arriving {
+            for (a, v) in labels {
+                g.bind(to, v, a);
+            }
+        }
+        for v in self.keys() {
+            let vtx = self.vertices.get(v).unwrap();
+            if vtx.persistence != Persistence::Empty {
+                g.put(v, &vtx.data);
+            }
+        }
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+use crate::Hex;
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn reverses_edges() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::from_str("bar").unwrap());
+    g.bind(1, 2, Label::from_str("xyz").unwrap());
+    g.put(2, &Hex::from(42));
+    let mut r = g.reverse().unwrap();
+    assert_eq!(3, r.len());
+    assert_eq!(0, r.kids(0).count());
+    assert_eq!(0, r.kid(1, Label::from_str("foo").unwrap()).unwrap());
+    assert_eq!(0, r.kid(2, Label::from_str("bar").unwrap()).unwrap());
+    assert_eq!(1, r.kid(2, Label::from_str("xyz").unwrap()).unwrap());
+    assert_eq!(42, r.data(2).unwrap().to_i64().unwrap());
+}
+
+#[test]
+fn turns_parents_into_kids() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..6 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 2, Label::Alpha(1));
+    g.bind(1, 2, Label::Alpha(0));
+    g.bind(2, 3, Label::Greek('φ'));
+    g.bind(3, 3, Label::Alpha(2));
+    g.bind(4, 2, Label::Alpha(2));
+    let r = g.reverse().unwrap();
+    for v in g.keys() {
+        let mut kids: Vec<(usize, Label)> = r
+            .kids_sorted(v)
+            .unwrap()
+            .into_iter()
+            .map(|(a, to)| (to, a))
+            .collect();
+        kids.sort_unstable();
+        assert_eq!(g.parents(v).unwrap(), kids, "ν{v}");
+    }
+}
+
+#[test]
+fn refuses_to_reverse_lossy_graph() {
+    let mut wide: Sodg<2> = Sodg::empty(16);
+    for v in 0..4 {
+        wide.add(v);
+    }
+    for v in 1..4 {
+        wide.bind(v, 0, Label::Alpha(v));
+    }
+    assert!(wide.reverse().is_err());
+    let mut same: Sodg<16> = Sodg::empty(16);
+    for v in 0..3 {
+        same.add(v);
+    }
+    same.bind(1, 0, Label::Alpha(0));
+    same.bind(2, 0, Label::Alpha(0));
+    let msg = same.reverse().unwrap_err().to_string();
+    assert!(msg.contains("edges 'α0' to ν0"), "{msg}");
+}
@@ -21,22 +21,132 @@
 use crate::{Hex, Script};
 use crate::{Label, Sodg};
 use anyhow::{anyhow, Context, Result};
-use lazy_static::lazy_static;
-use log::trace;
+use itertools::Itertools;
+use log::{info, trace};
 use regex::Regex;
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::LazyLock;
+use std::time::Instant;
+
+static LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^([A-Z]+) *\(((?:"(?:[^"\\]|\\.)*"|[^")])*)\)$"#).unwrap());
+static BLOCK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)^([A-Z]+) *\(((?:"(?:[^"\\]|\\.)*"|[^")])*)\) *\{(.*)\}$"#).unwrap()
+});
+static VAR: LazyLock<Regex> = LazyLock::new(|| Regex::new("\\$\\{([^}]+)\\}").unwrap());
+static INT_DATA: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new("^(-?[0-9]+)(?:i64(le|be)?)?$").unwrap());
+static FLOAT_DATA: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new("^(-?[0-9]+(?:\\.[0-9]+)?(?:[eE][-+]?[0-9]+)?)(?:(f64)(le|be)?)?$").unwrap()
+});
 
 impl Script {
     /// Make a new one, parsing a string with instructions.
     ///
     /// Instructions
-    /// must be separated by semicolon. There are just three of them
-    /// possible: `ADD`, `BIND`, and `PUT`. The arguments must be
+    /// must be separated by semicolon. The main three of them
+    /// are `ADD`, `BIND`, and `PUT`, while others are explained
+    /// below. The arguments must be
     /// separated by a comma. An argument may either be 1) a positive integer
     /// (possibly prepended by `ν`),
-    /// 2) a variable started with `$`, 3) an attribute name, or
-    /// 4) data in `XX-XX-...` hexadecimal format.
+    /// 2) a variable started with `$`, 3) an attribute name,
+    /// 4) data in `XX-XX-...` hexadecimal format, 5) a typed number,
+    /// like `42i64` or `3.14f64`, or 6) a string in double quotes,
+    /// like a path or a text to put into a vertex as UTF-8 bytes.
+    /// Inside a text put into a vertex (or referenced by `@`), the double
+    /// quote and the backslash must be escaped, like `"a \"quoted\" word"`,
+    /// while paths and messages are taken as they are, like `"C:\data"`.
+    ///
+    /// Typed numbers are stored as eight bytes in big-endian order,
+    /// unless the `le` suffix is used, like `42i64le`. The default order
+    /// may be changed for all further instructions by `ENCODING(le)`
+    /// (and back by `ENCODING(be)`), while the `be` suffix always makes
+    /// it big-endian. Floating point numbers must have a decimal point,
+    /// unless the `f64` suffix is used, like `1f64`. A number with a suffix is
+    /// never taken as hexadecimal data: `1f64be` is the number one, not
+    /// the bytes `1F-64-BE`. The suffix may be omitted, like `42` or `3.14`, which is the same as
+    /// `42i64` or `3.14f64`: bare digits are always an integer. Hexadecimal
+    /// data made only of decimal digits must have a dash, like `42-` or `01-02`.
+    ///
+    /// The `LOAD("graph.sodg", 100)` instruction reads a binary file,
+    /// previously created by [`Sodg::save`], and copies all its
+    /// vertices into the graph, adding `100` to their IDs. Nothing is
+    /// copied, if any of the new IDs doesn't fit into the capacity of the graph.
+    ///
+    /// The `DUMPTO("graph.sodg")` instruction saves the graph, as it is at
+    /// the moment, to a binary file, using [`Sodg::save`]. It writes nothing
+    /// when the script is only checked by [`Script::check_against`].
+    ///
+    /// The `ECHO("Created ${ν1}")` instruction prints the message to
+    /// the console, replacing `${ν1}` with the value of the `$ν1` variable,
+    /// and doesn't touch the graph.
+    ///
+    /// The `CLEARDATA(ν1)` instruction removes the data from `ν1`,
+    /// keeping the vertex and its edges.
+    ///
+    /// The `TRANSFORM(ν1, upper)` instruction changes the data in `ν1`
+    /// in place. The data is treated as a UTF-8 string and is turned
+    /// into `upper` or `lower` case, or is trimmed by `trim`. The `reverse`
+    /// operation reverses the order of bytes.
+    ///
+    /// The `PROBEDATA(ν1, 42i64)` instruction checks that the data in `ν1`
+    /// is exactly the same as provided, failing the deployment otherwise.
+    /// The graph is not modified and the vertex is not garbage collected.
+    ///
+    /// The `PROBEEQ(ν1, ν2)` instruction checks that the data in `ν1`
+    /// is exactly the same as the data in `ν2`, failing the deployment otherwise.
+    /// Neither of the vertices is garbage collected.
+    ///
+    /// The `PROBENONE(ν1, foo)` instruction checks that there is no edge
+    /// `foo` departing from `ν1`, failing the deployment otherwise.
+    ///
+    /// The `ACYCLIC()` instruction checks that there are no directed
+    /// cycles in the graph, failing the deployment otherwise.
+    ///
+    /// The `IFDATA(ν1) { ... }` block deploys the instructions inside
+    /// the curly brackets only if `ν1` has data at that moment. Blocks
+    /// may be nested and don't need a semicolon after the closing bracket.
+    ///
+    /// The `TIMED("setup") { ... }` block deploys the instructions inside
+    /// the curly brackets and logs, at the info level, how long it took.
+    ///
+    /// Instead of a vertex ID, the `@"hello"` reference may be used, which
+    /// points to the vertex with the data `hello`. If there is no such vertex
+    /// in the graph, it is created.
+    ///
+    /// The `ADDRANGE(ν10, 5)` instruction adds five vertices, from
+    /// `ν10` to `ν14`, failing if any of them already exists or
+    /// doesn't fit into the capacity of the graph.
+    ///
+    /// The `UNBIND(ν1, ν2, foo)` instruction removes the edge `foo`
+    /// from `ν1` to `ν2`, while `UNBIND(ν1, foo)` removes the edge `foo`
+    /// from `ν1`, no matter where it points to.
+    ///
+    /// The `REBIND(ν1, foo, ν3)` instruction makes the existing edge `foo`
+    /// from `ν1` point to `ν3`, failing if there is no such edge.
+    ///
+    /// The `USE(name)` instruction deploys the fragment of a script,
+    /// previously registered by [`Script::register`], as if its
+    /// instructions were written instead of it.
+    ///
+    /// The `BINDTO(ν1, "foo.bar", baz)` instruction finds the vertex
+    /// by the path `foo.bar` from `ν0`, using [`Sodg::find`], and makes
+    /// the edge `baz` from `ν1` to it, failing if the path can't be resolved.
+    ///
+    /// The `CSV("edges.csv")` instruction reads the file, where each line
+    /// is an edge, like `0,foo,1`, and binds all of them, adding
+    /// the vertices which are absent. Vertices may be written the same way
+    /// as in other instructions, for example, as variables.
+    ///
+    /// The `ATTRMAP(foo, bar)` instruction makes all further `BIND`
+    /// instructions use the `bar` label instead of `foo`.
+    ///
+    /// When the `rand` feature is enabled, the `RAND(ν1, 16, 42)` instruction
+    /// puts sixteen pseudo-random bytes into `ν1`, generated from the seed `42`.
+    /// The same seed always produces the same bytes.
     ///
     /// For example:
     ///
@@ -57,75 +167,461 @@ impl Script {
         Self {
             txt: s.to_string(),
             vars: HashMap::new(),
+            base: PathBuf::new(),
+            max_len: usize::MAX,
+            attrs: HashMap::new(),
+            interned: HashMap::new(),
+            little_endian: false,
+            fragments: HashMap::new(),
+            using: vec![],
+            dry: false,
         }
     }
 
+    /// Register a named fragment of a script, which may later be
+    /// deployed by the `USE(name)` instruction, as many times as necessary.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Script, Sodg};
+    /// let mut s = Script::from_str("USE(pair); USE(pair);")
+    ///     .register("pair", "ADD($ν1); ADD($ν2);");
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// s.deploy_to(&mut g).unwrap();
+    /// assert_eq!(2, g.len());
+    /// ```
+    ///
+    /// The fragment shares variables with the script, that's why
+    /// in the example above only two vertices are created.
+    #[must_use]
+    pub fn register(mut self, name: &str, text: &str) -> Self {
+        self.fragments.insert(name.to_string(), text.to_string());
+        self
+    }
+
+    /// Set the maximum length of a single command, in bytes.
+    ///
+    /// Commands that are longer will not be deployed, preventing
+    /// giant malformed inputs from being parsed. By default, there
+    /// is no limit.
+    #[must_use]
+    pub const fn max_command_len(mut self, max: usize) -> Self {
+        self.max_len = max;
+        self
+    }
+
+    /// Set the directory where relative paths mentioned
+    /// in the script are resolved.
+    ///
+    /// By default, they are resolved against the current working directory.
+    #[must_use]
+    pub fn with_base(mut self, dir: &Path) -> Self {
+        self.base = dir.to_path_buf();
+        self
+    }
+
+    /// Check whether the script can be deployed to the [`Sodg`],
+    /// without modifying neither the graph nor the script.
+    ///
+    /// The script is deployed to a copy of the graph, so `LOAD`
+    /// still reads files and `ECHO` still prints messages, while `DUMPTO`
+    /// doesn't write anything.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Script, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// let s = Script::from_str("ADD(1); BIND(0, 1, foo); BIND(0, 2, bar);");
+    /// let err = s.check_against(&g).unwrap_err();
+    /// assert!(err.to_string().contains("no.2"));
+    /// assert_eq!(1, g.len());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the script can't be deployed, an error will be returned,
+    /// explaining which command fails and why.
+    pub fn check_against<const N: usize>(&self, g: &Sodg<N>) -> Result<()> {
+        let mut s = self.clone();
+        s.dry = true;
+        s.deploy_to(&mut g.clone())?;
+        Ok(())
+    }
+
     /// Deploy the entire script to the [`Sodg`].
     ///
     /// # Errors
     ///
     /// If impossible to deploy, an error will be returned.
     pub fn deploy_to<const N: usize>(&mut self, g: &mut Sodg<N>) -> Result<usize> {
+        self.deploy_to_with_progress(g, |_, _| {})
+    }
+
+    /// Deploy the entire script to the [`Sodg`], reporting the progress.
+    ///
+    /// After each command, the callback gets the number of commands
+    /// already deployed and the total number of them in the script.
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Script, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// let mut s = Script::from_str("ADD(0); ADD(1);");
+    /// let mut done = vec![];
+    /// s.deploy_to_with_progress(&mut g, |d, t| done.push(format!("{d}/{t}"))).unwrap();
+    /// assert_eq!(vec!["1/2", "2/2"], done);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If impossible to deploy, an error will be returned.
+    pub fn deploy_to_with_progress<const N: usize>(
+        &mut self,
+        g: &mut Sodg<N>,
+        mut cb: impl FnMut(usize, usize),
+    ) -> Result<usize> {
+        let commands = self.commands();
+        let total = commands.len();
         let mut pos = 0;
-        for cmd in &self.commands() {
+        for (line, cmd) in &commands {
             trace!("#deploy_to: deploying command no.{} '{}'...", pos + 1, cmd);
-            self.deploy_one(cmd, g)
-                .with_context(|| format!("Failure at the command no.{pos}: '{cmd}'"))?;
+            if cmd.len() > self.max_len {
+                return Err(anyhow!(
+                    "The command no.{pos} is too long: {} bytes, while {} is the maximum, at line {line}",
+                    cmd.len(),
+                    self.max_len
+                ));
+            }
+            self.deploy_one(cmd, g).with_context(|| {
+                format!("Failure at line {line}, the command no.{pos}: '{cmd}'")
+            })?;
             pos += 1;
+            cb(pos, total);
         }
         Ok(pos)
     }
 
-    /// Get all commands.
-    fn commands(&self) -> Vec<String> {
-        lazy_static! {
-            static ref STRIP_COMMENTS: Regex = Regex::new("#.*\n").unwrap();
+    /// Get all commands, together with the numbers of lines
+    /// in the original text, where they start.
+    fn commands(&self) -> Vec<(usize, String)> {
+        Self::split_with_lines(&Self::strip_comments(self.txt.as_str()), ';')
+    }
+
+    /// Split the text by the separator, ignoring separators
+    /// inside double-quoted strings and curly brackets, trimming the parts and
+    /// dropping empty ones.
+    ///
+    /// A closing curly bracket at the top level also ends the part.
+    fn split_unquoted(text: &str, sep: char) -> Vec<String> {
+        Self::split_with_lines(text, sep)
+            .into_iter()
+            .map(|(_, t)| t)
+            .collect()
+    }
+
+    /// Split the text just like [`Script::split_unquoted`] does, but
+    /// also tell the number of the line where each part starts,
+    /// counting from one.
+    fn split_with_lines(text: &str, sep: char) -> Vec<(usize, String)> {
+        let mut parts = vec![];
+        let mut part = String::new();
+        let mut chars = text.chars();
+        let mut quoted = false;
+        let mut depth = 0;
+        let mut line = 1;
+        let mut start = None;
+        while let Some(c) = chars.next() {
+            if c == sep && !quoted && depth == 0 {
+                parts.push((start.take().unwrap_or(line), part.clone()));
+                part.clear();
+                continue;
+            }
+            part.push(c);
+            if c == '\n' {
+                line += 1;
+            } else if start.is_none() && !c.is_whitespace() {
+                start = Some(line);
+            }
+            if c == '"' {
+                quoted = !quoted;
+            } else if c == '\\' && quoted {
+                if let Some(n) = chars.next() {
+                    part.push(n);
+                }
+            } else if c == '{' && !quoted {
+                depth += 1;
+            } else if c == '}' && !quoted && depth > 0 {
+                depth -= 1;
+                if depth == 0 {
+                    parts.push((start.take().unwrap_or(line), part.clone()));
+                    part.clear();
+                }
+            }
         }
-        let text = self.txt.as_str();
-        let clean: &str = &STRIP_COMMENTS.replace_all(text, "");
-        clean
-            .split(';')
-            .map(str::trim)
-            .filter(|t| !t.is_empty())
-            .map(ToString::to_string)
+        parts.push((start.unwrap_or(line), part));
+        parts
+            .iter()
+            .map(|(n, t)| (*n, t.trim()))
+            .filter(|(_, t)| !t.is_empty())
+            .map(|(n, t)| (n, t.to_string()))
             .collect()
     }
 
+    /// Remove comments from the text: everything after `#` or `//` till
+    /// the end of the line, and everything between `/*` and `*/`.
+    ///
+    /// Line breaks are kept intact, while comment markers inside
+    /// double-quoted strings are not treated as comments.
+    fn strip_comments(text: &str) -> String {
+        let mut clean = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            if quoted {
+                clean.push(c);
+                if c == '\\' {
+                    if let Some(n) = chars.next() {
+                        clean.push(n);
+                    }
+                } else if c == '"' {
+                    quoted = false;
+                }
+                continue;
+            }
+            match c {
+                '"' => {
+                    quoted = true;
+                    clean.push(c);
+                }
+                '#' => while chars.next_if(|n| *n != '\n').is_some() {},
+                '/' if chars.peek() == Some(&'/') => {
+                    while chars.next_if(|n| *n != '\n').is_some() {}
+                }
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    let mut prev = ' ';
+                    for n in chars.by_ref() {
+                        if n == '\n' {
+                            clean.push(n);
+                        }
+                        if prev == '*' && n == '/' {
+                            break;
+                        }
+                        prev = n;
+                    }
+                }
+                _ => clean.push(c),
+            }
+        }
+        clean
+    }
+
     /// Deploy a single command to the [`Sodg`].
     ///
+    /// The commands that change vertices, edges, and data of the graph
+    /// are deployed here, while all others go to [`Script::deploy_aux`].
+    ///
     /// # Errors
     ///
     /// If impossible to deploy, an error will be returned.
     fn deploy_one<const N: usize>(&mut self, cmd: &str, g: &mut Sodg<N>) -> Result<()> {
-        lazy_static! {
-            static ref LINE: Regex = Regex::new("^([A-Z]+) *\\(([^)]*)\\)$").unwrap();
+        if let Some(cap) = BLOCK.captures(cmd) {
+            return self.deploy_block(&cap[1], &cap[2], &cap[3], g);
         }
         let cap = LINE
             .captures(cmd)
             .with_context(|| format!("Can't parse '{cmd}'"))?;
-        let args: Vec<String> = cap[2]
-            .split(',')
-            .map(str::trim)
-            .filter(|t| !t.is_empty())
-            .map(ToString::to_string)
-            .collect();
+        let args = Self::split_unquoted(&cap[2], ',');
         match &cap[1] {
             "ADD" => {
                 let v = self.parse(args.first().with_context(|| "V is expected")?, g)?;
                 g.add(v);
             }
+            "ADDRANGE" => {
+                let start = self.parse(args.first().with_context(|| "Start is expected")?, g)?;
+                let count =
+                    usize::from_str(args.get(1).with_context(|| "Count is expected")?.as_str())?;
+                Self::add_range(start, count, g)?;
+            }
             "BIND" => {
-                let v1 = self.parse(args.first().with_context(|| "V1 is expected")?, g)?;
-                let v2 = self.parse(args.get(1).with_context(|| "V2 is expected")?, g)?;
-                let a =
-                    Label::from_str(args.get(2).with_context(|| "Label is expected")?.as_str())?;
+                let v1 = self.parse_alive(args.first().with_context(|| "V1 is expected")?, g)?;
+                let v2 = self.parse_alive(args.get(1).with_context(|| "V2 is expected")?, g)?;
+                let a = self.parse_label(args.get(2).with_context(|| "Label is expected")?)?;
+                g.bind(v1, v2, a);
+            }
+            "BINDTO" => {
+                let v1 = self.parse_alive(args.first().with_context(|| "V1 is expected")?, g)?;
+                let path = Self::parse_string(args.get(1).with_context(|| "Path is expected")?)?;
+                let a = self.parse_label(args.get(2).with_context(|| "Label is expected")?)?;
+                let v2 = g
+                    .find(0, &path)
+                    .with_context(|| format!("Can't find '{path}' from ν0"))?;
                 g.bind(v1, v2, a);
             }
+            "UNBIND" => {
+                let v1 = self.parse_alive(args.first().with_context(|| "V1 is expected")?, g)?;
+                let (v2, a) = if args.len() > 2 {
+                    let v2 = self.parse(&args[1], g)?;
+                    (Some(v2), self.parse_label(&args[2])?)
+                } else {
+                    let a = args.get(1).with_context(|| "Label is expected")?;
+                    (None, self.parse_label(a)?)
+                };
+                let v2 = v2
+                    .or_else(|| g.kid(v1, a))
+                    .with_context(|| format!("There is no edge ν{v1}.{a}"))?;
+                g.unbind(v1, v2, a)?;
+            }
+            "REBIND" => {
+                let v1 = self.parse_alive(args.first().with_context(|| "V1 is expected")?, g)?;
+                let a = self.parse_label(args.get(1).with_context(|| "Label is expected")?)?;
+                let v2 = self.parse_alive(args.get(2).with_context(|| "V2 is expected")?, g)?;
+                g.rebind(v1, a, v2)?;
+            }
             "PUT" => {
-                let v = self.parse(args.first().with_context(|| "V is expected")?, g)?;
-                let d = Self::parse_data(args.get(1).with_context(|| "Data is expected")?)?;
+                let v = self.parse_alive(args.first().with_context(|| "V is expected")?, g)?;
+                let d = self.parse_data(args.get(1).with_context(|| "Data is expected")?)?;
                 g.put(v, &d);
             }
+            #[cfg(feature = "rand")]
+            "RAND" => {
+                let v = self.parse_alive(args.first().with_context(|| "V is expected")?, g)?;
+                let len =
+                    usize::from_str(args.get(1).with_context(|| "Length is expected")?.as_str())?;
+                let seed =
+                    u64::from_str(args.get(2).with_context(|| "Seed is expected")?.as_str())?;
+                g.put(v, &Hex::from_vec(Self::random_bytes(len, seed)));
+            }
+            "CLEARDATA" => {
+                let v = self.parse_alive(args.first().with_context(|| "V is expected")?, g)?;
+                g.clear_data(v)?;
+            }
+            "TRANSFORM" => {
+                let v = self.parse_alive(args.first().with_context(|| "V is expected")?, g)?;
+                let op = args.get(1).with_context(|| "Operation is expected")?;
+                if !g.has_data(v) {
+                    return Err(anyhow!("There is no data in ν{v}"));
+                }
+                let after = Self::transform(&Hex::from_slice(g.bytes_of(v)?), op)?;
+                g.put(v, &after);
+            }
+            cmd => {
+                return self.deploy_aux(cmd, &args, &cap[2], g);
+            }
+        }
+        Ok(())
+    }
+
+    /// Deploy an auxiliary command to the [`Sodg`]: the one that reads or
+    /// writes files (`USE`, `LOAD`, `CSV`, `DUMPTO`), changes the settings
+    /// of the script (`ATTRMAP`, `ENCODING`), checks the graph (`PROBEDATA`,
+    /// `PROBEEQ`, `PROBENONE`, `ACYCLIC`), or prints (`ECHO`).
+    ///
+    /// # Errors
+    ///
+    /// If impossible to deploy or the check fails, an error will be returned.
+    fn deploy_aux<const N: usize>(
+        &mut self,
+        cmd: &str,
+        args: &[String],
+        raw: &str,
+        g: &mut Sodg<N>,
+    ) -> Result<()> {
+        match cmd {
+            "USE" => {
+                let name = args.first().with_context(|| "Name is expected")?;
+                let text = self
+                    .fragments
+                    .get(name)
+                    .with_context(|| format!("There is no fragment '{name}'"))?
+                    .clone();
+                if self.using.contains(name) {
+                    return Err(anyhow!("The fragment '{name}' uses itself"));
+                }
+                self.using.push(name.clone());
+                let ret = self.deploy_body(&Self::strip_comments(&text), g);
+                self.using.pop();
+                ret.with_context(|| format!("Failure in the fragment '{name}'"))?;
+            }
+            "LOAD" => {
+                let path = Self::parse_string(args.first().with_context(|| "Path is expected")?)?;
+                let offset =
+                    usize::from_str(args.get(1).with_context(|| "Offset is expected")?.as_str())?;
+                let extra = Sodg::<N>::load(self.base.join(&path).as_path())?;
+                g.inject(&extra, offset)
+                    .with_context(|| format!("Can't load '{path}'"))?;
+            }
+            "DUMPTO" => {
+                let path = Self::parse_string(args.first().with_context(|| "Path is expected")?)?;
+                if !self.dry {
+                    g.save(self.base.join(path).as_path())?;
+                }
+            }
+            "CSV" => {
+                let path = Self::parse_string(args.first().with_context(|| "Path is expected")?)?;
+                let text = fs::read_to_string(self.base.join(&path))
+                    .with_context(|| format!("Can't read from '{path}'"))?;
+                for (pos, row) in text.lines().enumerate() {
+                    self.bind_row(row, g)
+                        .with_context(|| format!("Failure in line #{} of '{path}'", pos + 1))?;
+                }
+            }
+            "ATTRMAP" => {
+                let from =
+                    Label::from_str(args.first().with_context(|| "Label is expected")?.as_str())?;
+                let to =
+                    Label::from_str(args.get(1).with_context(|| "Label is expected")?.as_str())?;
+                self.attrs.insert(from, to);
+            }
+            "ENCODING" => {
+                self.little_endian = match args.first().map(String::as_str) {
+                    Some("le") => true,
+                    Some("be") => false,
+                    _ => return Err(anyhow!("Either 'be' or 'le' is expected")),
+                };
+            }
+            "PROBEDATA" => {
+                let v = self.parse_alive(args.first().with_context(|| "V is expected")?, g)?;
+                let d = self.parse_data(args.get(1).with_context(|| "Data is expected")?)?;
+                let actual = g.bytes_of(v)?;
+                if actual != d.bytes() {
+                    return Err(anyhow!(
+                        "The data in ν{v} is {}, while {d} is expected",
+                        Hex::from_slice(actual)
+                    ));
+                }
+            }
+            "PROBEEQ" => {
+                let a = self.parse_alive(args.first().with_context(|| "V1 is expected")?, g)?;
+                let b = self.parse_alive(args.get(1).with_context(|| "V2 is expected")?, g)?;
+                if !g.data_equal(a, b)? {
+                    return Err(anyhow!(
+                        "The data in ν{a} is {}, while in ν{b} it is {}",
+                        Hex::from_slice(g.bytes_of(a)?),
+                        Hex::from_slice(g.bytes_of(b)?)
+                    ));
+                }
+            }
+            "PROBENONE" => {
+                let v = self.parse_alive(args.first().with_context(|| "V is expected")?, g)?;
+                let a = self.parse_label(args.get(1).with_context(|| "Label is expected")?)?;
+                if let Some(k) = g.kid(v, a) {
+                    return Err(anyhow!("The edge ν{v}.{a} already points to ν{k}"));
+                }
+            }
+            "ACYCLIC" => g.assert_acyclic()?,
+            "ECHO" => {
+                let msg = Self::parse_string(raw.trim())?;
+                let out = VAR.replace_all(&msg, |c: &regex::Captures| {
+                    self.vars
+                        .get(&c[1])
+                        .map_or_else(|| c[0].to_string(), ToString::to_string)
+                });
+                println!("{out}");
+            }
             cmd => {
                 return Err(anyhow!("Unknown command: {cmd}"));
             }
@@ -133,30 +629,267 @@ impl Script {
         Ok(())
     }
 
+    /// Add `count` vertices, starting from `start`, making sure none
+    /// of them exists and all of them fit into the capacity of the graph.
+    ///
+    /// # Errors
+    ///
+    /// If any vertex exists or is out of capacity, an error will be returned
+    /// and nothing will be added.
+    fn add_range<const N: usize>(start: usize, count: usize, g: &mut Sodg<N>) -> Result<()> {
+        let cap = g.vertices.capacity();
+        let end = start
+            .checked_add(count)
+            .filter(|e| *e <= cap)
+            .with_context(|| {
+                format!(
+                    "The range of {count} vertices from ν{start} doesn't fit into capacity {cap}"
+                )
+            })?;
+        if let Some(v) = (start..end).find(|v| g.alive(*v)) {
+            return Err(anyhow!("Vertex ν{v} already exists"));
+        }
+        for v in start..end {
+            g.add(v);
+        }
+        Ok(())
+    }
+
+    /// Bind the edge from a row of CSV, like `0,foo,1`, adding
+    /// the vertices if they are absent. Empty rows are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the row can't be parsed, an error will be returned.
+    fn bind_row<const N: usize>(&mut self, row: &str, g: &mut Sodg<N>) -> Result<()> {
+        if row.trim().is_empty() {
+            return Ok(());
+        }
+        let cells: Vec<&str> = row.split(',').map(str::trim).collect();
+        let [from, a, to] = cells.as_slice() else {
+            return Err(anyhow!("Three cells are expected in '{row}'"));
+        };
+        let v1 = self.parse(from, g)?;
+        let a = self.parse_label(a)?;
+        let v2 = self.parse(to, g)?;
+        for v in [v1, v2] {
+            if !g.alive(v) {
+                g.add(v);
+            }
+        }
+        g.bind(v1, v2, a);
+        Ok(())
+    }
+
+    /// Deploy a block of commands, like `IFDATA(ν1) { ... }`, to the [`Sodg`].
+    ///
+    /// # Errors
+    ///
+    /// If impossible to deploy, an error will be returned.
+    fn deploy_block<const N: usize>(
+        &mut self,
+        head: &str,
+        args: &str,
+        body: &str,
+        g: &mut Sodg<N>,
+    ) -> Result<()> {
+        let args = Self::split_unquoted(args, ',');
+        match head {
+            "IFDATA" => {
+                let v = self.parse(args.first().with_context(|| "V is expected")?, g)?;
+                if g.has_data(v) {
+                    self.deploy_body(body, g)?;
+                }
+            }
+            "TIMED" => {
+                let label = Self::parse_string(args.first().with_context(|| "Label is expected")?)?;
+                let start = Instant::now();
+                self.deploy_body(body, g)?;
+                info!("{label}: deployed in {:?}", start.elapsed());
+            }
+            cmd => {
+                return Err(anyhow!("Unknown block: {cmd}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Deploy all commands from the body of a block.
+    ///
+    /// # Errors
+    ///
+    /// If impossible to deploy, an error will be returned.
+    fn deploy_body<const N: usize>(&mut self, body: &str, g: &mut Sodg<N>) -> Result<()> {
+        for (pos, cmd) in Self::split_unquoted(body, ';').iter().enumerate() {
+            self.deploy_one(cmd, g).with_context(|| {
+                format!("Failure at the command no.{pos} in the block: '{cmd}'")
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Apply the transformation, used by `TRANSFORM`, to the data.
+    ///
+    /// # Errors
+    ///
+    /// If the operation is unknown or the data is not a UTF-8 string,
+    /// an error will be returned.
+    fn transform(before: &Hex, op: &str) -> Result<Hex> {
+        Ok(match op {
+            "upper" => Hex::from_str_bytes(&before.to_utf8()?.to_uppercase()),
+            "lower" => Hex::from_str_bytes(&before.to_utf8()?.to_lowercase()),
+            "trim" => Hex::from_str_bytes(before.to_utf8()?.trim()),
+            "reverse" => Hex::from_vec(before.bytes().iter().rev().copied().collect()),
+            _ => return Err(anyhow!("Unknown transformation: {op}")),
+        })
+    }
+
+    /// Parse the label, replacing it with another one,
+    /// if it was registered by `ATTRMAP`.
+    ///
+    /// # Errors
+    ///
+    /// If impossible to parse, an error will be returned.
+    fn parse_label(&self, s: &str) -> Result<Label> {
+        let a = Label::from_str(s)?;
+        Ok(self.attrs.get(&a).copied().unwrap_or(a))
+    }
+
     /// Parse data.
     ///
     /// # Errors
     ///
     /// If impossible to parse, an error will be returned.
-    fn parse_data(s: &str) -> Result<Hex> {
-        lazy_static! {
-            static ref DATA_STRIP: Regex = Regex::new("[ \t\n\r\\-]").unwrap();
-            static ref DATA: Regex = Regex::new("^[0-9A-Fa-f]{2}([0-9A-Fa-f]{2})*$").unwrap();
-        }
-        let d: &str = &DATA_STRIP.replace_all(s, "");
-        if DATA.is_match(d) {
-            let bytes: Vec<u8> = (0..d.len())
-                .step_by(2)
-                .map(|i| u8::from_str_radix(&d[i..i + 2], 16).unwrap())
-                .collect();
-            Ok(Hex::from_vec(bytes))
-        } else {
-            Err(anyhow!("Can't parse data '{s}'"))
+    fn parse_data(&self, s: &str) -> Result<Hex> {
+        if s.starts_with('"') {
+            return Ok(Hex::from_vec(Self::parse_text(s)?.as_bytes().to_vec()));
+        }
+        let little = |e: Option<regex::Match>| e.map_or(self.little_endian, |e| e.as_str() == "le");
+        if let Some(cap) = INT_DATA.captures(s) {
+            let i = i64::from_str(&cap[1])
+                .with_context(|| format!("The integer '{s}' doesn't fit into i64"))?;
+            return Ok(Hex::from_vec(if little(cap.get(2)) {
+                i.to_le_bytes().to_vec()
+            } else {
+                i.to_be_bytes().to_vec()
+            }));
+        }
+        if let Some(cap) = FLOAT_DATA
+            .captures(s)
+            .filter(|cap| cap.get(2).is_some() || cap[1].contains('.'))
+        {
+            let f = f64::from_str(&cap[1]).with_context(|| format!("Can't parse float '{s}'"))?;
+            return Ok(Hex::from_vec(if little(cap.get(3)) {
+                f.to_le_bytes().to_vec()
+            } else {
+                f.to_be_bytes().to_vec()
+            }));
+        }
+        if s.is_empty() {
+            return Err(anyhow!("Data is expected"));
+        }
+        Hex::from_str(s).with_context(|| format!("Can't parse data '{s}'"))
+    }
+
+    /// Generate `len` pseudo-random bytes from the `seed`,
+    /// using the `SplitMix64` algorithm.
+    #[cfg(feature = "rand")]
+    fn random_bytes(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        let mut bytes = Vec::with_capacity(len);
+        while bytes.len() < len {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            bytes.extend(z.to_be_bytes().iter().take(len - bytes.len()));
         }
+        bytes
+    }
+
+    /// Parse a string in double quotes, like `"hello"`, into `hello`.
+    ///
+    /// Backslashes are kept as they are, so that paths like `"C:\data"`
+    /// and messages like `"one\ntwo"` are not changed.
+    ///
+    /// # Errors
+    ///
+    /// If the string is not quoted, an error will be returned.
+    fn parse_string(s: &str) -> Result<String> {
+        s.strip_prefix('"')
+            .and_then(|t| t.strip_suffix('"'))
+            .map(ToString::to_string)
+            .with_context(|| format!("A string in double quotes is expected: '{s}'"))
+    }
+
+    /// Parse a string in double quotes, like [`Script::parse_string`] does,
+    /// and unescape quotes and backslashes in it, like `\"` and `\\`.
+    ///
+    /// # Errors
+    ///
+    /// If the string is not quoted or escaping is broken, an error will be returned.
+    fn parse_text(s: &str) -> Result<String> {
+        let inner = Self::parse_string(s)?;
+        let mut out = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(n) if n == '"' || n == '\\' => out.push(n),
+                    _ => return Err(anyhow!("Broken escaping in the string: '{s}'")),
+                },
+                '"' => return Err(anyhow!("Unescaped quote in the string: '{s}'")),
+                _ => out.push(c),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Parse the vertex, like [`Script::parse`] does, and make sure
+    /// it exists in the graph.
+    ///
+    /// # Errors
+    ///
+    /// If impossible to parse or the vertex is absent, an error will be returned.
+    fn parse_alive<const N: usize>(&mut self, s: &str, g: &mut Sodg<N>) -> Result<usize> {
+        let v = self.parse(s, g)?;
+        if !g.alive(v) {
+            return Err(anyhow!("Vertex ν{v} is absent"));
+        }
+        Ok(v)
+    }
+
+    /// Find the vertex with the data equal to the provided string,
+    /// or create a new one, if there is no such vertex yet.
+    ///
+    /// If there are many vertices with the same data, the one with
+    /// the smallest ID is used. The result is remembered, so that all
+    /// further references to the same value resolve to the same vertex.
+    fn intern<const N: usize>(&mut self, value: &str, g: &mut Sodg<N>) -> usize {
+        if let Some(v) = self.interned.get(value) {
+            return *v;
+        }
+        let v = g
+            .keys()
+            .into_iter()
+            .sorted()
+            .find(|v| g.has_data(*v) && g.bytes_of(*v).is_ok_and(|b| b == value.as_bytes()))
+            .unwrap_or_else(|| {
+                let v = g.next_id();
+                g.add(v);
+                g.put(v, &Hex::from_str_bytes(value));
+                v
+            });
+        self.interned.insert(value.to_string(), v);
+        v
     }
 
     /// Parse `$ν5` into `5`, and `ν23` into `23`, and `42` into `42`.
     ///
+    /// The `@"hello"` is parsed into the ID of the vertex that
+    /// has "hello" as its data, see [`Script::intern`].
+    ///
     /// # Errors
     ///
     /// If impossible to parse, an error will be returned.
@@ -165,6 +898,10 @@ impl Script {
             .chars()
             .next()
             .with_context(|| "Empty identifier".to_string())?;
+        if head == '@' {
+            let value = Self::parse_text(&s[head.len_utf8()..])?;
+            return Ok(self.intern(&value, g));
+        }
         if head == '$' || head == 'ν' {
             let tail: String = s.chars().skip(1).collect::<Vec<_>>().into_iter().collect();
             if head == '$' {
@@ -198,3 +935,671 @@ fn simple_command() {
     assert_eq!("привет", g.data(1).unwrap().to_utf8().unwrap());
     assert_eq!(1, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
 }
+
+#[test]
+fn strips_slash_comments() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        // the root first
+        ADD(0); // and its kid
+        ADD(1); /* a block
+        comment; with a semicolon */ BIND(0, 1, foo);
+        # and the old style
+        PUT(1, CA-FE);
+        ",
+    );
+    assert_eq!(4, s.deploy_to(&mut g).unwrap());
+    assert_eq!(1, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert_eq!("CA-FE", g.data(1).unwrap().print());
+}
+
+#[cfg(test)]
+use tempfile::TempDir;
+
+#[test]
+fn loads_binary_graph() {
+    let tmp = TempDir::new().unwrap();
+    let mut extra: Sodg<16> = Sodg::empty(256);
+    extra.add(0);
+    extra.add(1);
+    extra.bind(0, 1, Label::from_str("bar").unwrap());
+    extra.put(1, &Hex::from(42));
+    extra.save(tmp.path().join("extra.sodg").as_path()).unwrap();
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(10);
+        BIND(0, 10, foo);
+        LOAD(\"extra.sodg\", 10);
+        ",
+    )
+    .with_base(tmp.path());
+    assert_eq!(4, s.deploy_to(&mut g).unwrap());
+    assert_eq!(3, g.len());
+    assert_eq!(11, g.kid(10, Label::from_str("bar").unwrap()).unwrap());
+    assert_eq!(42, g.data(11).unwrap().to_i64().unwrap());
+    let mut far = Script::from_str("LOAD(\"extra.sodg\", 255);").with_base(tmp.path());
+    let msg = format!("{:#}", far.deploy_to(&mut g).unwrap_err());
+    assert!(msg.contains("Can't load 'extra.sodg'"), "{msg}");
+    assert!(msg.contains("capacity 256"), "{msg}");
+    assert!(!g.alive(255));
+}
+
+#[test]
+fn loads_binary_graph_into_bound_vertex() {
+    let tmp = TempDir::new().unwrap();
+    let mut sub: Sodg<16> = Sodg::empty(256);
+    sub.add(0);
+    sub.add(1);
+    sub.bind(0, 1, Label::from_str("bar").unwrap());
+    sub.save(tmp.path().join("sub.sodg").as_path()).unwrap();
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(1);
+        BIND(0, 1, foo);
+        PUT(1, CA-FE);
+        LOAD(\"sub.sodg\", 1);
+        ",
+    )
+    .with_base(tmp.path());
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(Some(2), g.kid(1, Label::from_str("bar").unwrap()));
+    assert_eq!("CA-FE", g.data(1).unwrap().print());
+}
+
+#[test]
+fn binds_edges_from_csv() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(
+        tmp.path().join("edges.csv"),
+        "0,foo,1\n1, bar, 2\n\n$x,α0,0\n",
+    )
+    .unwrap();
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); CSV(\"edges.csv\");").with_base(tmp.path());
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(4, g.len());
+    assert_eq!(1, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert_eq!(2, g.kid(1, Label::from_str("bar").unwrap()).unwrap());
+    assert_eq!(1, g.parents(0).unwrap().len());
+    std::fs::write(tmp.path().join("broken.csv"), "0,foo\n").unwrap();
+    let mut broken = Script::from_str("CSV(\"broken.csv\");").with_base(tmp.path());
+    let msg = format!("{:#}", broken.deploy_to(&mut g).unwrap_err());
+    assert!(msg.contains("line #1"), "{msg}");
+}
+
+#[test]
+fn echoes_without_changes() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD($ν1);
+        ECHO(\"Added ${ν1}, and ${ν2} is unknown, so far\");
+        ",
+    );
+    assert_eq!(2, s.deploy_to(&mut g).unwrap());
+    assert_eq!(1, g.len());
+}
+
+#[test]
+fn rejects_too_long_command() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s =
+        Script::from_str("ADD(0); PUT(0, CA-FE-CA-FE-CA-FE-CA-FE-CA-FE);").max_command_len(16);
+    let r = s.deploy_to(&mut g);
+    assert!(r.is_err());
+    assert!(r.unwrap_err().to_string().contains("no.1 is too long"));
+    assert!(g.data(0).is_none());
+}
+
+#[test]
+fn keeps_semicolons_in_quotes() {
+    let s = Script::from_str("ADD(0); PUT(0, \"a;b\"); ECHO(\"x\\\";y\");");
+    assert_eq!(
+        vec!["ADD(0)", "PUT(0, \"a;b\")", "ECHO(\"x\\\";y\")"],
+        s.commands()
+            .into_iter()
+            .map(|(_, c)| c)
+            .collect::<Vec<String>>()
+    );
+}
+
+#[rstest]
+#[case("BIND(0, 7, foo);")]
+#[case("BIND(7, 0, foo);")]
+#[case("PUT(7, CA-FE);")]
+#[cfg_attr(feature = "rand", case("RAND(7, 8, 42);"))]
+fn refuses_to_touch_absent_vertex(#[case] cmd: &str) {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let mut s = Script::from_str(cmd);
+    let msg = format!("{:#}", s.deploy_to(&mut g).unwrap_err());
+    assert!(msg.contains("ν7 is absent"), "{msg}");
+    assert_eq!(1, g.len());
+    assert!(g.kids(0).next().is_none());
+    assert!(!g.has_data(7));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn puts_reproducible_random_data() {
+    let mut first: Sodg<16> = Sodg::empty(256);
+    Script::from_str("ADD(0); RAND(0, 20, 42);")
+        .deploy_to(&mut first)
+        .unwrap();
+    let mut second: Sodg<16> = Sodg::empty(256);
+    Script::from_str("ADD(0); RAND(0, 20, 42);")
+        .deploy_to(&mut second)
+        .unwrap();
+    let d = first.data(0).unwrap();
+    assert_eq!(20, d.len());
+    assert_eq!(d, second.data(0).unwrap());
+    let mut third: Sodg<16> = Sodg::empty(256);
+    Script::from_str("ADD(0); RAND(0, 20, 7);")
+        .deploy_to(&mut third)
+        .unwrap();
+    assert_ne!(d, third.data(0).unwrap());
+}
+
+#[test]
+fn puts_numbers_in_both_orders() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, 42i64le);
+        ADD(1); PUT(1, 42i64be);
+        ADD(2); PUT(2, 42i64);
+        ADD(3); PUT(3, -1.5f64le);
+        ADD(4); PUT(4, -1.5f64);
+        ADD(5); PUT(5, 1f64be);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    let le = g.data(0).unwrap().to_vec();
+    let be = g.data(1).unwrap().to_vec();
+    assert_eq!("2A-00-00-00-00-00-00-00", Hex::from_vec(le.clone()).print());
+    assert_eq!(42, g.data(2).unwrap().to_i64().unwrap());
+    assert_eq!(le.into_iter().rev().collect::<Vec<u8>>(), be);
+    let mut fle = g.data(3).unwrap().to_vec();
+    fle.reverse();
+    assert_eq!(fle, g.data(4).unwrap().to_vec());
+    assert!((g.data(5).unwrap().to_f64().unwrap() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn parses_hex_data_like_hex_does() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    Script::from_str("ADD(0); PUT(0, ca-fe ba BE);")
+        .deploy_to(&mut g)
+        .unwrap();
+    assert_eq!(Hex::from_str("CA-FE-BA-BE").unwrap(), g.data(0).unwrap());
+    assert!(Script::from_str("ADD(1); PUT(1, CA-F);")
+        .deploy_to(&mut g)
+        .is_err());
+}
+
+#[test]
+fn prefers_numbers_to_hex_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, 1f64le);
+        ADD(1); PUT(1, 1E05);
+        ADD(2); PUT(2, 1F-64-BE);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!("00-00-00-00-00-00-F0-3F", g.data(0).unwrap().print());
+    assert_eq!("1E-05", g.data(1).unwrap().print());
+    assert_eq!("1F-64-BE", g.data(2).unwrap().print());
+}
+
+#[test]
+fn puts_bare_numbers() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, 123);
+        ADD(1); PUT(1, -2.75);
+        ADD(2); PUT(2, -42);
+        ADD(3); PUT(3, CA-FE);
+        ADD(4); PUT(4, \"7\");
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(123, g.data(0).unwrap().to_i64().unwrap());
+    assert!((g.data(1).unwrap().to_f64().unwrap() + 2.75).abs() < f64::EPSILON);
+    assert_eq!(-42, g.data(2).unwrap().to_i64().unwrap());
+    assert_eq!("CA-FE", g.data(3).unwrap().print());
+    assert_eq!("7", g.data(4).unwrap().to_utf8().unwrap());
+}
+
+#[test]
+fn reads_bare_digits_as_integers() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, 42);
+        ADD(1); PUT(1, 10);
+        ADD(2); PUT(2, 42-);
+        ADD(3); PUT(3, 01-02);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(42, g.data(0).unwrap().to_i64().unwrap());
+    assert_eq!(10, g.data(1).unwrap().to_i64().unwrap());
+    assert_eq!("42", g.data(2).unwrap().print());
+    assert_eq!("01-02", g.data(3).unwrap().print());
+}
+
+#[test]
+fn rejects_too_big_integer() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); PUT(0, 99999999999999999999i64);");
+    assert!(s.deploy_to(&mut g).is_err());
+    let mut bare = Script::from_str("ADD(1); PUT(1, -99999999999999999999);");
+    let msg = format!("{:#}", bare.deploy_to(&mut g).unwrap_err());
+    assert!(msg.contains("doesn't fit into i64"), "{msg}");
+}
+
+#[test]
+fn binds_to_found_vertex() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(1); ADD(2); ADD(3);
+        BIND(0, 1, foo);
+        BIND(1, 2, bar);
+        BINDTO(3, \"foo.bar\", ref);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(2, g.kid(3, Label::from_str("ref").unwrap()).unwrap());
+    let mut broken = Script::from_str("BINDTO(3, \"foo.xyz\", bad);");
+    assert!(broken.deploy_to(&mut g).is_err());
+    assert!(g.kid(3, Label::from_str("bad").unwrap()).is_none());
+}
+
+#[test]
+fn rebinds_edge_to_new_target() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(1); ADD(2);
+        BIND(0, 1, foo);
+        REBIND(0, foo, 2);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(2, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert!(Script::from_str("REBIND(0, bar, 1);")
+        .deploy_to(&mut g)
+        .is_err());
+    assert!(Script::from_str("REBIND(0, foo, 7);")
+        .deploy_to(&mut g)
+        .is_err());
+    assert_eq!(2, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+}
+
+#[test]
+fn probes_equal_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, \"hello\");
+        ADD(1); PUT(1, 68-65-6C-6C-6F);
+        ADD(2); PUT(2, \"bye\");
+        PROBEEQ(0, 1);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    let msg = format!(
+        "{:#}",
+        Script::from_str("PROBEEQ(0, 2);")
+            .deploy_to(&mut g)
+            .unwrap_err()
+    );
+    assert!(msg.contains("ν0"), "{msg}");
+    assert!(g.has_data(0));
+}
+
+#[test]
+fn renames_attributes() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(1); ADD(2);
+        BIND(0, 1, foo);
+        ATTRMAP(foo, bar);
+        BIND(0, 2, foo);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(1, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert_eq!(2, g.kid(0, Label::from_str("bar").unwrap()).unwrap());
+}
+
+#[test]
+fn checks_against_graph() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let s = Script::from_str("ADD($ν1); BIND(0, $ν1, foo); BIND($ν1, 5, bar);");
+    let err = s.check_against(&g).unwrap_err();
+    assert!(format!("{err:?}").contains("ν5 is absent"), "{err:?}");
+    assert_eq!(1, g.len());
+    g.add(5);
+    s.check_against(&g).unwrap();
+    assert!(g.kid(0, Label::from_str("foo").unwrap()).is_none());
+}
+
+#[test]
+fn checks_without_dumping() {
+    let tmp = TempDir::new().unwrap();
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); DUMPTO(\"check.sodg\");").with_base(tmp.path());
+    s.check_against(&g).unwrap();
+    assert!(!tmp.path().join("check.sodg").exists());
+    s.deploy_to(&mut g).unwrap();
+    assert!(tmp.path().join("check.sodg").exists());
+}
+
+#[test]
+fn deploys_nested_ifdata_blocks() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(1); ADD(2);
+        PUT(1, CA-FE);
+        IFDATA(1) {
+            BIND(0, 1, foo);
+            IFDATA(2) { BIND(0, 2, bar); }
+            IFDATA(1) {
+                BIND(1, 2, xyz);
+            }
+        }
+        IFDATA(2) { BIND(0, 2, nope); }
+        ADD(3);
+        ",
+    );
+    assert_eq!(7, s.deploy_to(&mut g).unwrap());
+    assert_eq!(1, g.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert_eq!(2, g.kid(1, Label::from_str("xyz").unwrap()).unwrap());
+    assert!(g.kid(0, Label::from_str("bar").unwrap()).is_none());
+    assert!(g.kid(0, Label::from_str("nope").unwrap()).is_none());
+    assert_eq!(4, g.len());
+}
+
+#[test]
+fn dumps_intermediate_graph() {
+    let tmp = TempDir::new().unwrap();
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(1); BIND(0, 1, foo);
+        DUMPTO(\"middle.sodg\");
+        ADD(2); BIND(0, 2, bar);
+        ",
+    )
+    .with_base(tmp.path());
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(3, g.len());
+    let middle: Sodg<16> = Sodg::load(tmp.path().join("middle.sodg").as_path()).unwrap();
+    assert_eq!(2, middle.len());
+    assert_eq!(1, middle.kid(0, Label::from_str("foo").unwrap()).unwrap());
+    assert!(middle.kid(0, Label::from_str("bar").unwrap()).is_none());
+}
+
+#[test]
+fn probes_data_of_vertex() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); PUT(0, CA-FE); PROBEDATA(0, CA-FE);");
+    s.deploy_to(&mut g).unwrap();
+    let mut wrong = Script::from_str("PROBEDATA(0, BE-EF);");
+    let err = wrong.deploy_to(&mut g).unwrap_err();
+    assert!(format!("{err:#}").contains("BE-EF"));
+    assert_eq!("CA-FE", g.data(0).unwrap().print());
+}
+
+#[test]
+fn interns_vertices_by_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0);
+        BIND(0, @\"x\", foo);
+        BIND(0, @\"x\", bar);
+        BIND(0, @\"y\", baz);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(3, g.len());
+    let x = g.kid(0, Label::from_str("foo").unwrap()).unwrap();
+    assert_eq!(Some(x), g.kid(0, Label::from_str("bar").unwrap()));
+    assert_eq!("x", g.data(x).unwrap().to_utf8().unwrap());
+}
+
+#[test]
+fn deploys_timed_block() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0);
+        TIMED(\"timed-kids\") { ADD(1); BIND(0, 1, foo); IFDATA(1) { ADD(2); } }
+        ADD(3);
+        ",
+    );
+    assert_eq!(3, s.deploy_to(&mut g).unwrap());
+    assert_eq!(3, g.len());
+    assert_eq!(Some(1), g.kid(0, Label::from_str("foo").unwrap()));
+    assert!(crate::logged("timed-kids: deployed in "));
+}
+
+use rstest::rstest;
+
+#[rstest]
+#[case("upper", " Hello ", " HELLO ")]
+#[case("lower", " Hello ", " hello ")]
+#[case("trim", " Hello ", "Hello")]
+#[case("reverse", " Hello ", " olleH ")]
+fn transforms_data(#[case] op: &str, #[case] before: &str, #[case] after: &str) {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from_str_bytes(before));
+    let mut s = Script::from_str(format!("TRANSFORM(0, {op});").as_str());
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(after, g.data(0).unwrap().to_utf8().unwrap());
+}
+
+#[test]
+fn fails_on_unknown_transformation() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from_str_bytes("x"));
+    let mut s = Script::from_str("TRANSFORM(0, shuffle);");
+    assert!(s.deploy_to(&mut g).is_err());
+}
+
+#[test]
+fn switches_default_encoding() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, 1i64);
+        ENCODING(le);
+        ADD(1); PUT(1, 1i64);
+        ADD(2); PUT(2, 1i64be);
+        ENCODING(be);
+        ADD(3); PUT(3, 1i64);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!("00-00-00-00-00-00-00-01", g.data(0).unwrap().print());
+    assert_eq!("01-00-00-00-00-00-00-00", g.data(1).unwrap().print());
+    assert_eq!("00-00-00-00-00-00-00-01", g.data(2).unwrap().print());
+    assert_eq!("00-00-00-00-00-00-00-01", g.data(3).unwrap().print());
+}
+
+#[test]
+fn reports_progress_of_deployment() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); ADD(1); BIND(0, 1, foo); ADD(2); BIND(1, 2, bar);");
+    let mut seen = vec![];
+    let total = s
+        .deploy_to_with_progress(&mut g, |done, total| seen.push((done, total)))
+        .unwrap();
+    assert_eq!(5, total);
+    assert_eq!(vec![(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)], seen);
+}
+
+#[test]
+fn probes_free_slot() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); ADD(1); PROBENONE(0, foo); BIND(0, 1, foo);");
+    s.deploy_to(&mut g).unwrap();
+    let mut again = Script::from_str("PROBENONE(0, bar); PROBENONE(0, foo);");
+    let msg = format!("{:#}", again.deploy_to(&mut g).unwrap_err());
+    assert!(msg.contains("no.1"), "{msg}");
+    assert!(
+        msg.contains("The edge ν0.foo already points to ν1"),
+        "{msg}"
+    );
+}
+
+#[test]
+fn unbinds_edges() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); ADD(1);
+        BIND(0, 1, foo); BIND(0, 1, bar);
+        UNBIND(0, 1, foo);
+        UNBIND(0, bar);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert!(g.kid(0, Label::from_str("foo").unwrap()).is_none());
+    assert!(g.kid(0, Label::from_str("bar").unwrap()).is_none());
+    let mut wrong = Script::from_str("UNBIND(0, foo);");
+    assert!(wrong.deploy_to(&mut g).is_err());
+}
+
+#[test]
+fn uses_registered_fragment() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0);
+        USE(kid); BIND(0, $ν1, foo);
+        USE(kid); BIND(0, $ν1, bar);
+        ",
+    )
+    .register("kid", "ADD($ν1); PUT($ν1, CA-FE);");
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(2, g.len());
+    assert_eq!(
+        g.kid(0, Label::from_str("foo").unwrap()),
+        g.kid(0, Label::from_str("bar").unwrap())
+    );
+}
+
+#[test]
+fn refuses_recursive_fragment() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("USE(me);").register("me", "ADD(0); USE(me);");
+    let err = s.deploy_to(&mut g).unwrap_err();
+    assert!(format!("{err:#}").contains("uses itself"));
+}
+
+#[test]
+fn puts_quoted_string() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, \"привет\");
+        ADD(1); PUT(1, \"say \\\"hi\\\"; \\\\ bye\");
+        ADD(2); PUT(2, 68-69);
+        ",
+    );
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!("привет", g.data(0).unwrap().to_utf8().unwrap());
+    assert_eq!("say \"hi\"; \\ bye", g.data(1).unwrap().to_utf8().unwrap());
+    assert_eq!("hi", g.data(2).unwrap().to_utf8().unwrap());
+}
+
+#[test]
+fn parses_strings_with_parentheses() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "
+        ADD(0); PUT(0, \"a)b\");
+        ECHO(\"x (y)\");
+        TIMED(\"(setup)\") { ADD(1); PUT(1, \"c) \\\"d\\\" (e\"); }
+        ",
+    );
+    assert_eq!(4, s.deploy_to(&mut g).unwrap());
+    assert_eq!("a)b", g.data(0).unwrap().to_utf8().unwrap());
+    assert_eq!("c) \"d\" (e", g.data(1).unwrap().to_utf8().unwrap());
+}
+
+#[test]
+fn keeps_backslashes_in_paths_and_messages() {
+    assert_eq!(
+        "C:\\data\\g.sodg",
+        Script::parse_string("\"C:\\data\\g.sodg\"").unwrap()
+    );
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); ECHO(\"one\\ntwo\");");
+    assert_eq!(2, s.deploy_to(&mut g).unwrap());
+    let mut bad = Script::from_str("ADD(0); PUT(0, \"one\\ntwo\");");
+    assert!(bad.deploy_to(&mut g).is_err());
+}
+
+#[test]
+fn adds_range_of_vertices() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADDRANGE(ν3, 5);");
+    s.deploy_to(&mut g).unwrap();
+    assert_eq!(vec![3, 4, 5, 6, 7], g.keys());
+    let mut again = Script::from_str("ADDRANGE(0, 4);");
+    assert!(again.deploy_to(&mut g).is_err());
+    assert_eq!(5, g.len());
+    let mut wide = Script::from_str("ADDRANGE(250, 7);");
+    let msg = format!("{:#}", wide.deploy_to(&mut g).unwrap_err());
+    assert!(msg.contains("capacity 256"), "{msg}");
+    let mut huge = Script::from_str("ADDRANGE(ν10, 18446744073709551615);");
+    assert!(huge.deploy_to(&mut g).is_err());
+    assert_eq!(5, g.len());
+}
+
+#[test]
+fn clears_data_keeping_edges() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); ADD(1); BIND(0, 1, foo); PUT(1, CA-FE); CLEARDATA(1);");
+    s.deploy_to(&mut g).unwrap();
+    assert!(!g.has_data(1));
+    assert_eq!(2, g.len());
+    assert_eq!(Some(1), g.kid(0, Label::from_str("foo").unwrap()));
+    assert!(Script::from_str("CLEARDATA(7);").deploy_to(&mut g).is_err());
+}
+
+#[test]
+fn checks_graph_is_acyclic() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str("ADD(0); ADD(1); BIND(0, 1, foo); ACYCLIC();");
+    assert_eq!(4, s.deploy_to(&mut g).unwrap());
+    let mut again = Script::from_str("BIND(1, 0, back); ACYCLIC();");
+    let msg = format!("{:?}", again.deploy_to(&mut g).unwrap_err());
+    assert!(msg.contains("ν0 → ν1 → ν0"), "{msg}");
+}
+
+#[test]
+fn reports_line_of_broken_command() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let mut s = Script::from_str(
+        "ADD(0);\n\n# the next one is fine\nADD(1); /* and this\none too */ ADD(2);\n\n  BIND(0, 1,\n);\nADD(3);",
+    );
+    let msg = s.deploy_to(&mut g).unwrap_err().to_string();
+    assert!(msg.contains("at line 7, the command no.3"), "{msg}");
+}
@@ -18,14 +18,52 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Sodg;
-use anyhow::{Context, Result};
+use crate::{Hex, Label, Sodg, Vertex};
+use anyhow::{anyhow, Context, Result};
 use bincode::{deserialize, serialize};
 use log::trace;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::time::Instant;
 
+/// The bytes every file created by [`Sodg::save`] starts with.
+const MAGIC: &[u8; 4] = b"SODG";
+
+/// The version of the binary format, which is changed every time
+/// the layout of [`Sodg`] changes incompatibly.
+const VERSION: u8 = 1;
+
+/// The bytes every array created by [`Sodg::to_compact_bytes`] starts with.
+const COMPACT_MAGIC: &[u8; 4] = b"SODC";
+
+/// The version of the compact format, which is changed every time
+/// the layout of [`Compact`] changes incompatibly.
+const COMPACT_VERSION: u8 = 1;
+
+/// The graph, as it is serialized by [`Sodg::to_compact_bytes`].
+#[derive(Serialize, Deserialize)]
+struct Compact {
+    /// The capacity of the graph.
+    cap: usize,
+    /// All distinct labels of edges, referenced by their positions.
+    labels: Vec<Label>,
+    /// The vertices with their data and edges.
+    vertices: Vec<CompactVertex>,
+    /// The non-zero ordinals of edges, as sources, positions of labels, and ordinals.
+    ords: Vec<(usize, u32, usize)>,
+}
+
+/// A vertex, as it is serialized by [`Sodg::to_compact_bytes`].
+#[derive(Serialize, Deserialize)]
+struct CompactVertex {
+    id: usize,
+    data: Option<Hex>,
+    /// The edges, as positions of labels and targets.
+    edges: Vec<(u32, usize)>,
+}
+
 impl<const N: usize> Sodg<N> {
     /// Save the entire [`Sodg`] into a binary file.
     ///
@@ -33,12 +71,17 @@ impl<const N: usize> Sodg<N> {
     /// The function returns the size of the file just saved. In order
     /// to restore from the file, use [`Sodg::load`].
     ///
+    /// The file starts with a short header, which contains the
+    /// version of the format, followed by the graph serialized by `bincode`.
+    ///
     /// # Errors
     ///
     /// If impossible to save, an error will be returned.
     pub fn save(&self, path: &Path) -> Result<usize> {
         let start = Instant::now();
-        let bytes: Vec<u8> = serialize(self).with_context(|| "Failed to serialize")?;
+        let mut bytes: Vec<u8> = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend(serialize(self).with_context(|| "Failed to serialize")?);
         let size = bytes.len();
         fs::write(path, bytes).with_context(|| format!("Can't write to {}", path.display()))?;
         trace!(
@@ -56,14 +99,35 @@ impl<const N: usize> Sodg<N> {
     ///
     /// # Errors
     ///
-    /// If impossible to load, an error will be returned.
+    /// If impossible to load, an error will be returned. This also happens
+    /// if the file was saved in another version of the format or is truncated.
     pub fn load(path: &Path) -> Result<Self> {
         let start = Instant::now();
         let bytes =
             fs::read(path).with_context(|| format!("Can't read from {}", path.display()))?;
         let size = bytes.len();
-        let sodg: Self = deserialize(&bytes)
-            .with_context(|| format!("Can't deserialize from {}", path.display()))?;
+        let body = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .with_context(|| format!("The file {} is not a SODG", path.display()))?;
+        let (version, body) = body.split_first().with_context(|| {
+            format!(
+                "The file {} is truncated, there is no version",
+                path.display()
+            )
+        })?;
+        if *version != VERSION {
+            return Err(anyhow!(
+                "The file {} is saved in the format version {version}, while {VERSION} is expected",
+                path.display()
+            ));
+        }
+        let mut sodg: Self = deserialize(body).with_context(|| {
+            format!(
+                "Can't deserialize {size} bytes from {}, maybe it's truncated",
+                path.display()
+            )
+        })?;
+        sodg.enable_reverse_index(true);
         trace!(
             "Deserialized {} vertices ({} bytes) from {} in {:?}",
             sodg.len(),
@@ -73,16 +137,165 @@ impl<const N: usize> Sodg<N> {
         );
         Ok(sodg)
     }
-}
 
-#[cfg(test)]
-use tempfile::TempDir;
+    /// Serialize the entire [`Sodg`] into a compact array of bytes,
+    /// which may be restored by [`Sodg::from_compact_bytes`].
+    ///
+    /// Unlike [`Sodg::save`], the labels of edges are stored only once, while
+    /// edges refer to them by their positions. Absent vertices and edges
+    /// pointing to them are not stored at all. This makes the array much smaller,
+    /// if the same labels are used in many vertices. For example:
+    ///
+    /// ```
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// let bytes = g.to_compact_bytes().unwrap();
+    /// let after : Sodg<16> = Sodg::from_compact_bytes(&bytes).unwrap();
+    /// assert_eq!(Some(1), after.kid(0, Label::Alpha(0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If impossible to serialize, an error will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn to_compact_bytes(&self) -> Result<Vec<u8>> {
+        let mut positions: HashMap<Label, u32> = HashMap::new();
+        let mut compact = Compact {
+            cap: self.vertices.capacity(),
+            labels: vec![],
+            vertices: vec![],
+            ords: vec![],
+        };
+        let mut keys = self.keys();
+        keys.sort_unstable();
+        for v in keys {
+            let vtx = self.vertices.get(v).unwrap();
+            let mut edges = vec![];
+            for (a, to) in vtx.edges.iter().filter(|(_, to)| self.alive(**to)) {
+                let pos = *positions.entry(*a).or_insert_with(|| {
+                    compact.labels.push(*a);
+                    u32::try_from(compact.labels.len() - 1).unwrap()
+                });
+                edges.push((pos, *to));
+                let ord = self.ord(v, *a);
+                if ord > 0 {
+                    compact.ords.push((v, pos, ord));
+                }
+            }
+            let data = self.has_data(v).then(|| vtx.data.clone());
+            compact.vertices.push(CompactVertex { id: v, data, edges });
+        }
+        let mut bytes: Vec<u8> = COMPACT_MAGIC.to_vec();
+        bytes.push(COMPACT_VERSION);
+        bytes.extend(serialize(&compact).with_context(|| "Failed to serialize")?);
+        Ok(bytes)
+    }
 
-#[cfg(test)]
-use crate::Hex;
+    /// Restore the [`Sodg`] from an array of bytes previously
+    /// created by [`Sodg::to_compact_bytes`].
+    ///
+    /// Only vertices, their data, edges, and ordinals of edges are restored.
+    /// The branches of vertices, which are used for garbage collection
+    /// in [`Sodg::data`], are not preserved: they are built anew by
+    /// [`Sodg::bind`] and [`Sodg::put`]. The next ID, returned
+    /// by [`Sodg::next_id`], is not preserved either. The capacity of the restored
+    /// graph is just enough for its largest vertex, no matter what capacity
+    /// is written in the array.
+    ///
+    /// # Errors
+    ///
+    /// If the array is broken, was created by another version
+    /// of the format, has a vertex with more than `N` edges, or
+    /// has a vertex out of its capacity, an error will be returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if vertices are absent (should never happen, though).
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self> {
+        let body = bytes
+            .strip_prefix(COMPACT_MAGIC.as_slice())
+            .with_context(|| "The bytes are not a compact SODG")?;
+        let (version, body) = body
+            .split_first()
+            .with_context(|| "The bytes are truncated, there is no version")?;
+        if *version != COMPACT_VERSION {
+            return Err(anyhow!(
+                "The bytes are in the compact format version {version}, while {COMPACT_VERSION} is expected"
+            ));
+        }
+        let compact: Compact = deserialize(body)
+            .with_context(|| format!("Can't deserialize {} bytes", bytes.len()))?;
+        for vtx in &compact.vertices {
+            if vtx.id >= compact.cap {
+                return Err(anyhow!(
+                    "The ν{} is out of capacity {}",
+                    vtx.id,
+                    compact.cap
+                ));
+            }
+            if vtx.edges.len() > N {
+                return Err(anyhow!(
+                    "The ν{} has {} edges, while no more than {N} are allowed",
+                    vtx.id,
+                    vtx.edges.len()
+                ));
+            }
+        }
+        let cap = compact
+            .vertices
+            .iter()
+            .map(|vtx| vtx.id + 1)
+            .max()
+            .unwrap_or(0)
+            .min(compact.cap);
+        if cap > isize::MAX.unsigned_abs() / size_of::<Vertex<N>>() {
+            return Err(anyhow!("The capacity {cap} is too big"));
+        }
+        let label = |pos: u32| {
+            compact
+                .labels
+                .get(pos as usize)
+                .copied()
+                .with_context(|| format!("There is no label #{pos}"))
+        };
+        let mut g = Self::empty(cap);
+        for vtx in &compact.vertices {
+            g.add(vtx.id);
+        }
+        for vtx in &compact.vertices {
+            for (pos, to) in &vtx.edges {
+                if !g.alive(*to) {
+                    return Err(anyhow!("The edge from ν{} points to absent ν{to}", vtx.id));
+                }
+                g.bind(vtx.id, *to, label(*pos)?);
+            }
+        }
+        for (v, pos, o) in &compact.ords {
+            let a = label(*pos)?;
+            let to = g
+                .alive(*v)
+                .then(|| g.kid(*v, a))
+                .flatten()
+                .with_context(|| format!("There is no edge ν{v}.{a} to order"))?;
+            g.put_edge(*v, a, to, *o);
+        }
+        for vtx in compact.vertices {
+            if let Some(d) = vtx.data {
+                g.put(vtx.id, &d);
+            }
+        }
+        Ok(g)
+    }
+}
 
 #[cfg(test)]
-use crate::Label;
+use tempfile::TempDir;
 
 #[cfg(test)]
 use std::str::FromStr;
@@ -110,3 +323,114 @@ fn saves_and_loads() {
     let after: Sodg<1> = Sodg::load(file.as_path()).unwrap();
     assert_eq!(g.inspect(0).unwrap(), after.inspect(0).unwrap());
 }
+
+#[test]
+fn saves_and_loads_everything() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.add(1);
+    g.add(2);
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::Alpha(0));
+    g.put(1, &Hex::from_str_bytes("hello"));
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("foo.sodg");
+    let size = g.save(file.as_path()).unwrap();
+    assert_eq!(size as u64, file.metadata().unwrap().len());
+    let mut after: Sodg<16> = Sodg::load(file.as_path()).unwrap();
+    assert_eq!(g.keys(), after.keys());
+    assert_eq!(g.to_edge_vec(), after.to_edge_vec());
+    assert_eq!("hello", after.data(1).unwrap().to_utf8().unwrap());
+    assert!(after.data(2).is_none());
+}
+
+#[test]
+fn refuses_to_load_truncated_file() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    g.put(0, &Hex::from_str_bytes("hello"));
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("foo.sodg");
+    g.save(file.as_path()).unwrap();
+    let bytes = std::fs::read(&file).unwrap();
+    std::fs::write(&file, &bytes[..bytes.len() / 2]).unwrap();
+    let err = Sodg::<16>::load(file.as_path()).err().unwrap();
+    assert!(format!("{err:#}").contains("truncated"), "{err:#}");
+}
+
+#[test]
+fn refuses_to_load_other_version() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("foo.sodg");
+    g.save(file.as_path()).unwrap();
+    let mut bytes = std::fs::read(&file).unwrap();
+    bytes[MAGIC.len()] = VERSION + 1;
+    std::fs::write(&file, bytes).unwrap();
+    let err = Sodg::<16>::load(file.as_path()).err().unwrap();
+    assert!(err.to_string().contains("version"), "{err:#}");
+}
+
+#[test]
+fn shrinks_label_heavy_graph() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let labels: Vec<Label> = ["foo", "bar", "φ", "α0"]
+        .into_iter()
+        .map(|a| Label::from_str(a).unwrap())
+        .collect();
+    for v in 0..200 {
+        g.add(v);
+    }
+    for b in 0..12 {
+        for v in b * 16..b * 16 + 15 {
+            for (i, a) in labels.iter().enumerate() {
+                g.bind(v, b * 16 + (v + i + 1) % 16, *a);
+            }
+        }
+    }
+    g.bind_ordered(3, 4, Label::Alpha(5), 7).unwrap();
+    g.put(7, &Hex::from_str_bytes("seven"));
+    g.put(8, &Hex::empty());
+    let tmp = TempDir::new().unwrap();
+    let plain = g.save(tmp.path().join("plain.sodg").as_path()).unwrap();
+    let bytes = g.to_compact_bytes().unwrap();
+    assert!(bytes.len() * 2 < plain, "{} vs {plain}", bytes.len());
+    let after: Sodg<16> = Sodg::from_compact_bytes(&bytes).unwrap();
+    assert_eq!(g.keys(), after.keys());
+    assert_eq!(g.to_edge_vec(), after.to_edge_vec());
+    assert_eq!(g.kids_ordered(3).unwrap(), after.kids_ordered(3).unwrap());
+    for v in g.keys() {
+        assert_eq!(g.has_data(v), after.has_data(v), "ν{v}");
+        assert_eq!(g.bytes_of(v).unwrap(), after.bytes_of(v).unwrap(), "ν{v}");
+    }
+    assert!(Sodg::<16>::from_compact_bytes(&bytes[..bytes.len() / 2]).is_err());
+    assert!(Sodg::<16>::from_compact_bytes(b"SODG").is_err());
+    assert!(Sodg::<4>::from_compact_bytes(&bytes).is_err());
+}
+
+#[test]
+fn rejects_compact_bytes_with_broken_capacity() {
+    let forge = |cap: usize, id: usize| {
+        let compact = Compact {
+            cap,
+            labels: vec![],
+            vertices: vec![CompactVertex {
+                id,
+                data: None,
+                edges: vec![],
+            }],
+            ords: vec![],
+        };
+        let mut bytes: Vec<u8> = COMPACT_MAGIC.to_vec();
+        bytes.push(COMPACT_VERSION);
+        bytes.extend(serialize(&compact).unwrap());
+        bytes
+    };
+    assert!(Sodg::<16>::from_compact_bytes(&forge(6, 5)).is_ok());
+    assert!(Sodg::<16>::from_compact_bytes(&forge(5, 5)).is_err());
+    assert!(Sodg::<16>::from_compact_bytes(&forge(usize::MAX, usize::MAX - 1)).is_err());
+    let g = Sodg::<16>::from_compact_bytes(&forge(usize::MAX, 5)).unwrap();
+    assert_eq!(6, g.vertices.capacity());
+    assert!(g.alive(5));
+}
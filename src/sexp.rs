@@ -0,0 +1,100 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Sodg;
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+impl<const N: usize> Sodg<N> {
+    /// Print the graph as a nested S-expression, starting from the vertex `root`.
+    ///
+    /// Each vertex becomes a list with its ID, followed by its kids,
+    /// each in a list together with the label of the edge, sorted by labels.
+    /// A vertex that was already printed is not printed again, but is
+    /// referenced as `#` with its ID. For example, for this code:
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use sodg::{Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::from_str("foo").unwrap());
+    /// g.bind(1, 0, Label::from_str("up").unwrap());
+    /// assert_eq!("(0 (foo (1 (up #0))))", g.to_sexp(0).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If vertex `root` is absent, an `Err` will be returned.
+    pub fn to_sexp(&self, root: usize) -> Result<String> {
+        if !self.alive(root) {
+            return Err(anyhow!("Can't find ν{root}"));
+        }
+        let mut seen = HashSet::new();
+        let mut sexp = String::new();
+        self.sexp_rec(root, &mut seen, &mut sexp)?;
+        Ok(sexp)
+    }
+
+    fn sexp_rec(&self, v: usize, seen: &mut HashSet<usize>, sexp: &mut String) -> std::fmt::Result {
+        if !seen.insert(v) {
+            return write!(sexp, "#{v}");
+        }
+        write!(sexp, "({v}")?;
+        let kids = self
+            .kids(v)
+            .filter(|(_, to)| self.alive(**to))
+            .sorted_by_key(|(a, _)| **a);
+        for (a, to) in kids {
+            write!(sexp, " ({a} ")?;
+            self.sexp_rec(*to, seen, sexp)?;
+            sexp.push(')');
+        }
+        sexp.push(')');
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+use crate::Label;
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn prints_tree_as_sexp() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..4 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::from_str("foo").unwrap());
+    g.bind(0, 2, Label::from_str("bar").unwrap());
+    g.bind(2, 3, Label::Alpha(0));
+    g.bind(3, 0, Label::from_str("back").unwrap());
+    assert_eq!(
+        "(0 (bar (2 (α0 (3 (back #0))))) (foo (1)))",
+        g.to_sexp(0).unwrap()
+    );
+    assert_eq!("(1)", g.to_sexp(1).unwrap());
+    assert!(g.to_sexp(7).is_err());
+}
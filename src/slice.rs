@@ -19,7 +19,7 @@
 // SOFTWARE.
 
 use crate::{Label, Sodg};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use log::trace;
 use std::collections::HashSet;
 
@@ -27,9 +27,28 @@ impl<const N: usize> Sodg<N> {
     /// Take a slice of the graph, keeping only the vertex specified
     /// by the locator and its kids, recursively found in the entire graph.
     ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.add(2);
+    /// g.bind(1, 2, Label::Alpha(0));
+    /// g.put(2, &Hex::from(42));
+    /// let mut slice = g.slice(1).unwrap();
+    /// assert_eq!(2, slice.len());
+    /// assert_eq!(42, slice.data(2).unwrap().to_i64().unwrap());
+    /// ```
+    ///
+    /// The IDs of vertices, the labels of edges, and the data are preserved.
+    /// Edges pointing to absent vertices are not copied.
+    ///
     /// # Errors
     ///
-    /// If impossible to slice, an error will be returned.
+    /// If vertex `v` is absent, an `Err` will be returned.
     #[allow(clippy::use_self)]
     pub fn slice(&self, v: usize) -> Result<Self> {
         let g: Sodg<N> = self.slice_some(v, |_, _, _| true)?;
@@ -48,12 +67,15 @@ impl<const N: usize> Sodg<N> {
     ///
     /// # Errors
     ///
-    /// There could be errors too.
+    /// If vertex `v` is absent, an `Err` will be returned.
     ///
     /// # Panics
     ///
-    /// If impossible to slice, an error will be returned.
+    /// May panic if vertices are absent (should never happen, though).
     pub fn slice_some(&self, v: usize, p: impl Fn(usize, usize, Label) -> bool) -> Result<Self> {
+        if !self.alive(v) {
+            return Err(anyhow!("Can't find ν{v}"));
+        }
         let mut todo = HashSet::new();
         let mut done = HashSet::new();
         todo.insert(v);
@@ -65,7 +87,7 @@ impl<const N: usize> Sodg<N> {
             for v in before {
                 done.insert(v);
                 for e in &self.vertices.get(v).unwrap().edges {
-                    if done.contains(e.1) {
+                    if done.contains(e.1) || !self.alive(*e.1) {
                         continue;
                     }
                     if !p(v, *e.1, *e.0) {
@@ -77,17 +99,21 @@ impl<const N: usize> Sodg<N> {
             }
         }
         let mut ng = Self::empty(self.vertices.capacity());
+        for v1 in &done {
+            ng.add(*v1);
+        }
         for (v1, vtx) in self.vertices.iter().filter(|(v, _)| done.contains(v)) {
-            if done.contains(&v1) {
-                ng.add(v1);
-            }
             for (k, v2) in &vtx.edges {
                 if done.contains(v2) {
-                    ng.add(*v2);
                     ng.bind(v1, *v2, *k);
                 }
             }
         }
+        for v1 in done {
+            if self.has_data(v1) {
+                ng.put(v1, &self.vertices.get(v1).unwrap().data);
+            }
+        }
         trace!(
             "#slice_some: taken {} vertices out of {} at ν{v}",
             ng.len(),
@@ -138,3 +164,38 @@ fn skips_some_vertices() {
     assert_eq!(2, slice.len());
     assert_eq!(1, slice.kids(0).count());
 }
+
+#[cfg(test)]
+use crate::Hex;
+
+#[test]
+fn slices_branch_with_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    for v in 0..7 {
+        g.add(v);
+    }
+    g.bind(0, 1, Label::from_str("left").unwrap());
+    g.bind(0, 2, Label::from_str("right").unwrap());
+    g.bind(1, 3, Label::Alpha(0));
+    g.bind(3, 4, Label::Alpha(0));
+    g.bind(4, 1, Label::from_str("up").unwrap());
+    g.bind(2, 5, Label::Alpha(0));
+    g.bind(3, 6, Label::Alpha(1));
+    g.remove(6);
+    g.put(4, &Hex::from_str_bytes("leaf"));
+    let mut slice = g.slice(1).unwrap();
+    let mut keys = slice.keys();
+    keys.sort_unstable();
+    assert_eq!(vec![1, 3, 4], keys);
+    assert_eq!(
+        vec![
+            (1, Label::Alpha(0), 3),
+            (3, Label::Alpha(0), 4),
+            (4, Label::from_str("up").unwrap(), 1),
+        ],
+        slice.to_edge_vec()
+    );
+    assert_eq!("leaf", slice.data(4).unwrap().to_utf8().unwrap());
+    assert!(g.slice(6).is_err());
+    assert!(g.slice(100).is_err());
+}
@@ -0,0 +1,90 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Snapshot, Sodg};
+
+impl<const N: usize> Sodg<N> {
+    /// Save the entire state of the graph, in order to
+    /// return back to it later, using [`Sodg::restore`].
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::Sodg;
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// let s = g.snapshot();
+    /// g.add(1);
+    /// g.restore(s);
+    /// assert_eq!(1, g.len());
+    /// ```
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot<N> {
+        Snapshot { g: self.clone() }
+    }
+
+    /// Replace the entire state of the graph with the one
+    /// previously saved by [`Sodg::snapshot`].
+    ///
+    /// The query logger, set by [`Sodg::set_query_logger`], is not a part
+    /// of the state and stays in place.
+    pub fn restore(&mut self, s: Snapshot<N>) {
+        let logger = self.logger.take();
+        *self = s.g;
+        self.logger = logger;
+    }
+}
+
+#[cfg(test)]
+use crate::{Hex, Label};
+
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn restores_from_snapshot() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    let v = g.next_id();
+    g.add(v);
+    g.put(v, &Hex::from(42));
+    let s = g.snapshot();
+    let k = g.next_id();
+    g.add(k);
+    g.bind(v, k, Label::Alpha(0));
+    g.put(v, &Hex::from(7));
+    g.restore(s);
+    assert_eq!(1, g.len());
+    assert!(g.kid(v, Label::Alpha(0)).is_none());
+    assert_eq!(42, g.data(v).unwrap().to_i64().unwrap());
+    assert_eq!(k, g.next_id());
+}
+
+#[test]
+fn keeps_query_logger_after_restore() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(0);
+    let log = Arc::new(Mutex::new(vec![]));
+    let sink = log.clone();
+    g.set_query_logger(Box::new(move |q| sink.lock().unwrap().push(q.to_string())));
+    let s = g.snapshot();
+    g.restore(s);
+    g.data(0);
+    assert_eq!(vec!["data(ν0)"], *log.lock().unwrap());
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2022-2025 Objectionary.com
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Hex, Persistence, Sodg, VertexView, BRANCH_NONE};
+use itertools::Itertools;
+
+impl<const N: usize> Sodg<N> {
+    /// Iterate over all vertices of the graph, in ascending order of their IDs.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use sodg::{Hex, Label, Sodg};
+    /// let mut g : Sodg<16> = Sodg::empty(256);
+    /// g.add(0);
+    /// g.add(1);
+    /// g.bind(0, 1, Label::Alpha(0));
+    /// g.put(1, &Hex::from(42));
+    /// let views = g.iter_vertices().collect::<Vec<_>>();
+    /// assert_eq!(1, views[0].out_degree());
+    /// assert_eq!(42, views[1].data().unwrap().to_i64().unwrap());
+    /// ```
+    ///
+    /// The data is not taken from the vertices, so they are not
+    /// submitted to garbage collection.
+    pub fn iter_vertices(&self) -> impl Iterator<Item = VertexView<'_>> + '_ {
+        self.vertices
+            .iter()
+            .filter(|(_, vtx)| vtx.branch != BRANCH_NONE)
+            .sorted_by_key(|(v, _)| *v)
+            .map(|(v, vtx)| VertexView {
+                id: v,
+                data: if vtx.persistence == Persistence::Empty {
+                    None
+                } else {
+                    Some(&vtx.data)
+                },
+                degree: vtx.edges.len(),
+            })
+    }
+}
+
+impl<'a> VertexView<'a> {
+    /// The ID of the vertex.
+    #[must_use]
+    pub const fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The data of the vertex, if it has any.
+    #[must_use]
+    pub const fn data(&self) -> Option<&'a Hex> {
+        self.data
+    }
+
+    /// The number of edges departing from the vertex.
+    #[must_use]
+    pub const fn out_degree(&self) -> usize {
+        self.degree
+    }
+}
+
+#[cfg(test)]
+use crate::Label;
+
+#[test]
+fn iterates_vertices_with_data() {
+    let mut g: Sodg<16> = Sodg::empty(256);
+    g.add(2);
+    g.add(0);
+    g.add(1);
+    g.bind(0, 1, Label::Alpha(0));
+    g.bind(0, 2, Label::Alpha(1));
+    g.put(2, &Hex::from_str_bytes("hi"));
+    let views = g
+        .iter_vertices()
+        .map(|w| (w.id(), w.data().map(Hex::print), w.out_degree()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (0, None, 2),
+            (1, None, 0),
+            (2, Some("68-69".to_string()), 0)
+        ],
+        views
+    );
+}